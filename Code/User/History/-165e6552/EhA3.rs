@@ -0,0 +1,179 @@
+use crate::eval::Score;
+use crate::state::board::{Color, Piece};
+
+/// Material values per piece type
+pub const MATERIAL: [Score; 6] = [
+    Score::new(82, 94),
+    Score::new(337, 281),
+    Score::new(365, 297),
+    Score::new(477, 512),
+    Score::new(1025, 936),
+    Score::new(0, 0),
+];
+
+// Tables are laid out as seen from White's side of the board (a8 first, h1 last),
+// so White looks squares up with `sq ^ 56` and Black with `sq` directly
+
+#[rustfmt::skip]
+const PAWN_MG: [i32; 64] = [
+      0,   0,   0,   0,   0,   0,   0,   0,
+     60,  70,  50,  60,  60,  50,  70,  60,
+     15,  20,  30,  35,  35,  30,  20,  15,
+      0,   5,  10,  25,  25,  10,   5,   0,
+     -5,   0,   5,  20,  20,   5,   0,  -5,
+     -5,  -5,   0,   5,   5,  -5,  -5,  -5,
+     -5,   5,   5, -20, -20,  10,  10,  -5,
+      0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const PAWN_EG: [i32; 64] = [
+      0,   0,   0,   0,   0,   0,   0,   0,
+    130, 125, 115, 105, 105, 115, 125, 130,
+     70,  70,  60,  50,  50,  60,  70,  70,
+     25,  20,  15,  10,  10,  15,  20,  25,
+     10,   5,   0,  -5,  -5,   0,   5,  10,
+      0,   0,  -5,   0,   0,  -5,   0,   0,
+      5,   5,   5,  10,  10,   5,   5,   5,
+      0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_MG: [i32; 64] = [
+    -90, -50, -35, -30, -30, -35, -50, -90,
+    -45, -25,   5,  10,  10,   5, -25, -45,
+    -30,  10,  25,  35,  35,  25,  10, -30,
+    -20,  10,  25,  35,  35,  25,  10, -20,
+    -20,   5,  20,  25,  25,  20,   5, -20,
+    -25,   0,  15,  15,  15,  15,   0, -25,
+    -35, -20,   0,   5,   5,   0, -20, -35,
+    -60, -25, -35, -25, -25, -35, -25, -60,
+];
+
+#[rustfmt::skip]
+const KNIGHT_EG: [i32; 64] = [
+    -60, -40, -25, -20, -20, -25, -40, -60,
+    -35, -20,  -5,   0,   0,  -5, -20, -35,
+    -25,  -5,  10,  15,  15,  10,  -5, -25,
+    -20,   0,  15,  25,  25,  15,   0, -20,
+    -20,   0,  15,  25,  25,  15,   0, -20,
+    -25,  -5,   5,  15,  15,   5,  -5, -25,
+    -35, -20,  -5,   0,   0,  -5, -20, -35,
+    -55, -40, -25, -20, -20, -25, -40, -55,
+];
+
+#[rustfmt::skip]
+const BISHOP_MG: [i32; 64] = [
+    -25, -10, -20, -15, -15, -20, -10, -25,
+    -20,   5,   0,   0,   0,   0,   5, -20,
+    -10,  15,  20,  20,  20,  20,  15, -10,
+     -5,  10,  15,  25,  25,  15,  10,  -5,
+     -5,  10,  10,  20,  20,  10,  10,  -5,
+      0,  10,  10,  10,  10,  10,  10,   0,
+      0,  15,  10,   5,   5,  10,  15,   0,
+    -20,  -5, -15, -10, -10, -15,  -5, -20,
+];
+
+#[rustfmt::skip]
+const BISHOP_EG: [i32; 64] = [
+    -15, -10, -10,  -5,  -5, -10, -10, -15,
+    -10,   0,   0,   0,   0,   0,   0, -10,
+     -5,   5,  10,  10,  10,  10,   5,  -5,
+     -5,   5,  10,  15,  15,  10,   5,  -5,
+     -5,   5,  10,  15,  15,  10,   5,  -5,
+     -5,   5,  10,  10,  10,  10,   5,  -5,
+    -10,   0,   0,   5,   5,   0,   0, -10,
+    -15, -10, -10,  -5,  -5, -10, -10, -15,
+];
+
+#[rustfmt::skip]
+const ROOK_MG: [i32; 64] = [
+     25,  25,  25,  30,  30,  25,  25,  25,
+     30,  35,  40,  45,  45,  40,  35,  30,
+      0,   5,  10,  10,  10,  10,   5,   0,
+    -10,  -5,   0,   5,   5,   0,  -5, -10,
+    -15, -10,  -5,   0,   0,  -5, -10, -15,
+    -20, -10,  -5,  -5,  -5,  -5, -10, -20,
+    -25, -15,  -5,   0,   0,  -5, -15, -25,
+    -10,  -5,   5,  15,  15,  10,  -5, -10,
+];
+
+#[rustfmt::skip]
+const ROOK_EG: [i32; 64] = [
+     10,  10,  10,  10,  10,  10,  10,  10,
+     10,  10,  10,  10,  10,  10,  10,  10,
+      5,   5,   5,   5,   5,   5,   5,   5,
+      0,   0,   5,   5,   5,   5,   0,   0,
+      0,   0,   0,   0,   0,   0,   0,   0,
+     -5,  -5,  -5,  -5,  -5,  -5,  -5,  -5,
+    -10, -10,  -5,  -5,  -5,  -5, -10, -10,
+    -10,  -5,   0,   0,   0,   0,  -5, -10,
+];
+
+#[rustfmt::skip]
+const QUEEN_MG: [i32; 64] = [
+    -20, -10, -10,  -5,  -5, -10, -10, -20,
+    -15, -25,  -5,   0,   0,  -5, -25, -15,
+    -10,  -5,   5,   5,   5,   5,  -5, -10,
+    -10,  -5,   0,   5,   5,   0,  -5, -10,
+     -5,  -5,   0,   5,   5,   0,  -5,  -5,
+    -10,   5,   5,   0,   0,   5,   0, -10,
+    -15,   0,   5,  10,  10,   0,   0, -15,
+    -20, -15, -10,   5,  -5, -10, -15, -20,
+];
+
+#[rustfmt::skip]
+const QUEEN_EG: [i32; 64] = [
+    -10,   0,   5,  10,  10,   5,   0, -10,
+     -5,   5,  15,  20,  20,  15,   5,  -5,
+      0,  10,  20,  25,  25,  20,  10,   0,
+      5,  15,  25,  30,  30,  25,  15,   5,
+      5,  15,  25,  30,  30,  25,  15,   5,
+      0,  10,  15,  20,  20,  15,  10,   0,
+    -10,  -5,   0,   5,   5,   0,  -5, -10,
+    -20, -15, -10, -10, -10, -10, -15, -20,
+];
+
+#[rustfmt::skip]
+const KING_MG: [i32; 64] = [
+    -60, -60, -60, -70, -70, -60, -60, -60,
+    -50, -50, -60, -70, -70, -60, -50, -50,
+    -40, -40, -50, -60, -60, -50, -40, -40,
+    -40, -40, -50, -60, -60, -50, -40, -40,
+    -30, -35, -40, -50, -50, -40, -35, -30,
+    -15, -20, -25, -35, -35, -25, -20, -15,
+     15,  10, -10, -30, -30, -15,  10,  15,
+     20,  35,  15, -20,   0, -10,  35,  25,
+];
+
+#[rustfmt::skip]
+const KING_EG: [i32; 64] = [
+    -70, -45, -35, -25, -25, -35, -45, -70,
+    -35, -10,   0,   5,   5,   0, -10, -35,
+    -20,   5,  20,  25,  25,  20,   5, -20,
+    -20,  10,  25,  35,  35,  25,  10, -20,
+    -25,   5,  20,  30,  30,  20,   5, -25,
+    -30,  -5,  10,  20,  20,  10,  -5, -30,
+    -40, -20,  -5,   5,   5,  -5, -20, -40,
+    -60, -45, -30, -20, -25, -30, -45, -60,
+];
+
+const TABLES: [(&[i32; 64], &[i32; 64]); 6] = [
+    (&PAWN_MG, &PAWN_EG),
+    (&KNIGHT_MG, &KNIGHT_EG),
+    (&BISHOP_MG, &BISHOP_EG),
+    (&ROOK_MG, &ROOK_EG),
+    (&QUEEN_MG, &QUEEN_EG),
+    (&KING_MG, &KING_EG),
+];
+
+/// Material plus piece-square bonus for `piece` of `color` standing on `sq`
+pub fn value(piece: Piece, color: Color, sq: u8) -> Score {
+    let index = match color {
+        Color::White => sq as usize ^ 56,
+        Color::Black => sq as usize,
+    };
+    let (mg, eg) = TABLES[piece as usize];
+
+    MATERIAL[piece as usize] + Score::new(mg[index], eg[index])
+}
@@ -0,0 +1,865 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::Instant;
+
+use lazy_static::lazy_static;
+
+use crate::eval::evaluate;
+use crate::eval::pawns::{passed_pawns, PawnTable};
+use crate::movegen::pawns::{RANK_2, RANK_7};
+use crate::movegen::generate::generate_legal_moves;
+use crate::movepick::{Heuristics, MovePicker};
+use crate::see::{see, SEE_VALUES};
+use crate::state::board::{Board, Color, Piece, Square};
+use crate::state::make_move::make_move;
+use crate::state::null_move::{make_null_move, undo_null_move};
+use crate::state::r#move::{from_square, is_capture, is_en_passant, is_promotion, move_to_uci, to_square, Move};
+use crate::state::state::GameState;
+use crate::state::undo_move::undo_move;
+use crate::time::{TimeLimits, TimeManager};
+use crate::tt::{Bound, TranspositionTable, TtEntry};
+
+pub const INFINITY: i32 = 32_000;
+pub const MATE: i32 = 31_000;
+pub const MAX_PLY: usize = 128;
+
+/// Any score beyond this is a forced mate
+pub const MATE_BOUND: i32 = MATE - MAX_PLY as i32;
+
+/// Most search threads the `Threads` option accepts
+pub const MAX_THREADS: usize = 256;
+
+// Half-width of the first aspiration window, in centipawns
+const ASPIRATION_WINDOW: i32 = 25;
+
+// Quiescence skips captures that can't lift the score this close to alpha
+const DELTA_MARGIN: i32 = 200;
+
+// Poll the clock and the stop flag every this many nodes
+const CHECK_INTERVAL: u64 = 2048;
+
+// Lazy SMP: helper threads skip some iterations so they spread out over different depths.
+// Helper i follows entry (i - 1) % 20: depth d is skipped when (d + phase) / size is odd.
+const SKIP_SIZE: [u32; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+const SKIP_PHASE: [u32; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
+
+// Reverse futility: a static eval this far above beta per ply of depth is trusted to hold
+const RFP_MAX_DEPTH: u32 = 6;
+const RFP_MARGIN: i32 = 80;
+
+// Null-move pruning: minimum depth, and how few pieces make zugzwang worth verifying
+const NMP_MIN_DEPTH: u32 = 3;
+const NMP_VERIFY_MAX_PIECES: u32 = 2;
+
+// Futility pruning: quiet moves can't make up this much at the last few plies
+const FUTILITY_MARGINS: [i32; 4] = [0, 100, 200, 300];
+
+// Late move pruning: quiet moves searched at depth d before the rest are skipped
+const LMP_MAX_DEPTH: u32 = 3;
+const LMP_BASE: usize = 3;
+
+// Singular extensions: minimum depth, and how far below the TT score alternatives must stay
+const SE_MIN_DEPTH: u32 = 8;
+const SE_MARGIN_PER_PLY: i32 = 2;
+
+// Most extensions one line may collect (also never more than the iteration depth)
+const MAX_EXTENSIONS: u32 = 16;
+
+// Late move reductions apply from this depth and this many moves in
+const LMR_MIN_DEPTH: u32 = 3;
+const LMR_MIN_MOVES: usize = 3;
+
+lazy_static! {
+    // Late move reductions by [depth][moves searched], growing with the log of both
+    static ref LMR_TABLE: [[u32; 64]; 64] = {
+        let mut table = [[0; 64]; 64];
+        for (depth, row) in table.iter_mut().enumerate().skip(1) {
+            for (moves, reduction) in row.iter_mut().enumerate().skip(1) {
+                *reduction = (0.75 + (depth as f64).ln() * (moves as f64).ln() / 2.25) as u32;
+            }
+        }
+        table
+    };
+}
+
+/// When the search has to stop
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SearchLimits {
+    pub depth: Option<u32>,
+    pub time: Option<TimeLimits>,
+}
+
+/// Pruning and reduction techniques that can be switched off (UCI options), for A/B testing
+#[derive(Clone, Copy, Debug)]
+pub struct SearchOptions {
+    pub null_move: bool,
+    pub late_move_reductions: bool,
+    pub reverse_futility: bool,
+    pub futility: bool,
+    pub late_move_pruning: bool,
+    pub check_extension: bool,
+    pub singular_extension: bool,
+    pub pawn_push_extension: bool,
+    pub recapture_extension: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            null_move: true,
+            late_move_reductions: true,
+            reverse_futility: true,
+            futility: true,
+            late_move_pruning: true,
+            check_extension: true,
+            singular_extension: true,
+            pawn_push_extension: true,
+            recapture_extension: true,
+        }
+    }
+}
+
+/// Outcome of a completed search
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u32,
+    pub nodes: u64,
+    pub pv: Vec<Move>,
+}
+
+// What the search threads have in common
+struct Shared<'a> {
+    tt: &'a TranspositionTable,
+    stop: &'a AtomicBool,  // raised from outside, by `stop` or `quit`
+    abort: AtomicBool,     // raised by the main thread once it is done, to call the helpers back
+    nodes: Vec<AtomicU64>, // per thread, published every CHECK_INTERVAL nodes
+    start: Instant,
+}
+
+impl Shared<'_> {
+    fn total_nodes(&self) -> u64 {
+        self.nodes.iter().map(|nodes| nodes.load(Ordering::Relaxed)).sum()
+    }
+}
+
+// One search thread. Everything it writes is its own apart from the transposition table.
+struct Searcher<'a> {
+    id: usize, // 0 is the main thread, which manages time and reports
+    shared: &'a Shared<'a>,
+    board: Board,
+    limits: SearchLimits,
+    options: SearchOptions,
+    time: TimeManager,
+    pawn_table: PawnTable,
+    nodes: u64,
+    stopped: bool,
+
+    // Triangular PV table: pv[ply] holds the line from ply onward
+    pv: Vec<[Move; MAX_PLY]>,
+    pv_length: [usize; MAX_PLY],
+
+    // Principal variation of the last completed iteration, searched first
+    previous_pv: Vec<Move>,
+
+    // Move ordering state, and the move played at each ply (for countermoves)
+    heuristics: Heuristics,
+    move_stack: [Move; MAX_PLY],
+
+    // Nodes spent below each root move, by from * 64 + to (for time management)
+    root_nodes: Vec<u64>,
+
+    // Null moves stay off below this ply while a null-move cutoff is being verified
+    nmp_min_ply: usize,
+
+    // Extensions spent on the current line, against a budget tied to the iteration depth
+    root_depth: u32,
+    extensions: u32,
+
+    // Move left out of the singular-extension search at each ply (0 = none)
+    excluded: [Move; MAX_PLY],
+}
+
+/// Iterative deepening negamax search from `board` until a limit is hit or `stop` is set
+///
+/// With `threads` > 1 this is Lazy SMP: helper threads search the same position with their
+/// own board copy and move-ordering tables, sharing only the transposition table, and the
+/// move to play is then chosen by a vote among all threads.
+pub fn search(
+    board: &Board,
+    limits: SearchLimits,
+    options: SearchOptions,
+    threads: usize,
+    stop: &AtomicBool,
+    tt: &TranspositionTable,
+) -> SearchResult {
+    tt.new_search();
+
+    let threads = threads.max(1);
+    let shared = Shared {
+        tt,
+        stop,
+        abort: AtomicBool::new(false),
+        nodes: (0..threads).map(|_| AtomicU64::new(0)).collect(),
+        start: Instant::now(),
+    };
+
+    let results: Vec<SearchResult> = thread::scope(|scope| {
+        let helpers: Vec<_> = (1..threads)
+            .map(|id| {
+                let shared = &shared;
+
+                // Helpers keep going until the main thread calls them back
+                let limits = SearchLimits { time: None, ..limits };
+                scope.spawn(move || Searcher::new(id, shared, board, limits, options).iterate())
+            })
+            .collect();
+
+        let main = Searcher::new(0, &shared, board, limits, options).iterate();
+        shared.abort.store(true, Ordering::Relaxed);
+
+        let mut results = vec![main];
+        results.extend(helpers.into_iter().map(|helper| helper.join().expect("search thread panicked")));
+        results
+    });
+
+    let best = vote(&results);
+    let mut result = results[best].clone();
+    result.nodes = shared.total_nodes();
+
+    // The GUI has only seen the main thread's lines so far
+    if best != 0 {
+        report(&shared, &result);
+    }
+
+    result
+}
+
+// Index of the thread whose result gets played. Each thread votes for its best move,
+// weighted by depth and by how much better its score is than the worst one reported.
+// A proven mate overrides the vote, and ties go to the main thread.
+fn vote(results: &[SearchResult]) -> usize {
+    let finished = || results.iter().filter(|r| r.depth > 0 && r.best_move.is_some());
+    let min_score = finished().map(|r| r.score).min().unwrap_or(0);
+
+    let mut votes: HashMap<Move, i64> = HashMap::new();
+    for r in finished() {
+        let weight = (r.score - min_score + 14) as i64 * r.depth as i64;
+        *votes.entry(r.best_move.unwrap()).or_default() += weight;
+    }
+    let votes_for = |r: &SearchResult| r.best_move.and_then(|m| votes.get(&m).copied()).unwrap_or(0);
+
+    let mut best = 0;
+    for (i, r) in results.iter().enumerate().skip(1) {
+        if r.depth == 0 || r.best_move.is_none() {
+            continue;
+        }
+
+        let current = &results[best];
+        let better = if r.score >= MATE_BOUND || current.score >= MATE_BOUND {
+            r.score > current.score
+        } else {
+            votes_for(r) > votes_for(current)
+        };
+
+        if better {
+            best = i;
+        }
+    }
+
+    best
+}
+
+impl<'a> Searcher<'a> {
+    fn new(id: usize, shared: &'a Shared<'a>, board: &Board, limits: SearchLimits, options: SearchOptions) -> Self {
+        Self {
+            id,
+            shared,
+            board: board.clone(),
+            limits,
+            options,
+            time: TimeManager::new(shared.start, limits.time),
+            pawn_table: PawnTable::new(),
+            nodes: 0,
+            stopped: false,
+            pv: vec![[0; MAX_PLY]; MAX_PLY],
+            pv_length: [0; MAX_PLY],
+            previous_pv: Vec::new(),
+            heuristics: Heuristics::new(),
+            move_stack: [0; MAX_PLY],
+            root_nodes: vec![0; 64 * 64],
+            nmp_min_ply: 0,
+            root_depth: 0,
+            extensions: 0,
+            excluded: [0; MAX_PLY],
+        }
+    }
+
+    // Iterative deepening; returns the last iteration that was searched to the end
+    fn iterate(&mut self) -> SearchResult {
+        // Always have something to play, even if the first iteration is cut short
+        let root_moves = generate_legal_moves(&self.board);
+        let mut result = SearchResult {
+            best_move: root_moves.iter().next().copied(),
+            score: 0,
+            depth: 0,
+            nodes: 0,
+            pv: Vec::new(),
+        };
+
+        if root_moves.is_empty() {
+            return result;
+        }
+
+        let max_depth = self.limits.depth.unwrap_or(MAX_PLY as u32 - 1).min(MAX_PLY as u32 - 1);
+
+        for depth in 1..=max_depth {
+            // Helpers leave out some depths so that the threads don't all search the same tree
+            if self.id > 0 {
+                let i = (self.id - 1) % SKIP_SIZE.len();
+                if ((depth + SKIP_PHASE[i]) / SKIP_SIZE[i]) % 2 == 1 {
+                    continue;
+                }
+            }
+
+            let score = self.aspiration(depth, result.score);
+
+            // A partial iteration can't be trusted; keep the last complete one
+            if self.stopped {
+                break;
+            }
+
+            result.score = score;
+            result.depth = depth;
+            result.nodes = self.nodes;
+            result.pv = self.pv[0][..self.pv_length[0]].to_vec();
+            result.best_move = result.pv.first().copied().or(result.best_move);
+            self.previous_pv = result.pv.clone();
+
+            if self.id == 0 {
+                self.publish_nodes();
+                report(self.shared, &result);
+            }
+
+            // No point searching deeper once a forced mate has been found
+            if score.abs() >= MATE_BOUND && depth as i32 >= MATE - score.abs() {
+                break;
+            }
+
+            // Or once the time manager judges another iteration not worth starting
+            if let Some(best) = result.best_move {
+                let best_nodes = self.root_nodes[from_square(best) as usize * 64 + to_square(best) as usize];
+                if self.time.iteration_done(best, score, best_nodes, self.nodes) {
+                    break;
+                }
+            }
+        }
+
+        self.publish_nodes();
+        result
+    }
+
+    // Search with a narrow window around the previous score, widening on failure
+    fn aspiration(&mut self, depth: u32, previous: i32) -> i32 {
+        self.root_depth = depth;
+
+        if depth < 4 {
+            return self.negamax(depth, 0, -INFINITY, INFINITY);
+        }
+
+        let mut delta = ASPIRATION_WINDOW;
+        let mut alpha = (previous - delta).max(-INFINITY);
+        let mut beta = (previous + delta).min(INFINITY);
+
+        loop {
+            let score = self.negamax(depth, 0, alpha, beta);
+            if self.stopped {
+                return score;
+            }
+
+            if score <= alpha {
+                alpha = (score - delta).max(-INFINITY);
+            } else if score >= beta {
+                beta = (score + delta).min(INFINITY);
+            } else {
+                return score;
+            }
+
+            delta *= 2;
+        }
+    }
+
+    fn negamax(&mut self, depth: u32, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.pv_length[ply] = ply;
+
+        if self.nodes.is_multiple_of(CHECK_INTERVAL) && self.should_stop() {
+            self.stopped = true;
+        }
+        if self.stopped {
+            return 0;
+        }
+
+        self.nodes += 1;
+
+        if depth == 0 || ply >= MAX_PLY - 1 {
+            return self.quiescence(ply, alpha, beta);
+        }
+
+        // Cut off on a deep enough stored bound, except in PV nodes where we want the full line.
+        // A singular-extension search has a move excluded, so the entry doesn't describe it.
+        let is_pv = beta - alpha > 1;
+        let excluded = self.excluded[ply];
+        let entry = self.shared.tt.probe(self.board.hash, ply);
+        if let Some(entry) = entry
+            && !is_pv
+            && excluded == 0
+            && entry.depth as u32 >= depth
+        {
+            match entry.bound {
+                Bound::Exact => return entry.score,
+                Bound::Lower if entry.score >= beta => return entry.score,
+                Bound::Upper if entry.score <= alpha => return entry.score,
+                _ => {}
+            }
+        }
+
+        let in_check = self.board.in_check();
+        let static_eval = if in_check {
+            -INFINITY
+        } else {
+            evaluate(&self.board, &mut self.pawn_table)
+        };
+
+        // Node-level pruning, only where a wrong guess can't corrupt the principal variation
+        if !is_pv && !in_check && excluded == 0 {
+            // Reverse futility: so far above beta that a quiet reply can't bring it back
+            if self.options.reverse_futility
+                && depth <= RFP_MAX_DEPTH
+                && beta.abs() < MATE_BOUND
+                && static_eval - RFP_MARGIN * depth as i32 >= beta
+            {
+                return beta;
+            }
+
+            if let Some(score) = self.null_move(depth, ply, beta, static_eval) {
+                return score;
+            }
+        }
+
+        // Try the stored best move first, falling back to the previous principal variation
+        let tt_move = entry
+            .and_then(|entry| entry.best_move)
+            .or_else(|| self.previous_pv.get(ply).copied());
+        let previous = if ply > 0 && self.move_stack[ply - 1] != 0 {
+            Some(self.move_stack[ply - 1])
+        } else {
+            None
+        };
+        let mut picker = MovePicker::new(&self.board, &self.heuristics, ply, tt_move, previous);
+
+        // Quiet moves at the last plies that can't realistically raise alpha
+        let futile = self.options.futility
+            && !is_pv
+            && !in_check
+            && (depth as usize) < FUTILITY_MARGINS.len()
+            && alpha.abs() < MATE_BOUND
+            && static_eval + FUTILITY_MARGINS[depth as usize] <= alpha;
+        let lmp_limit = if self.options.late_move_pruning && !is_pv && !in_check && depth <= LMP_MAX_DEPTH {
+            LMP_BASE + (depth * depth) as usize
+        } else {
+            usize::MAX
+        };
+
+        let original_alpha = alpha;
+        let mut best_move = None;
+        let mut moves_seen = 0;
+        let mut moves_searched = 0;
+        let mut quiets_tried = Vec::new();
+        let mut state = GameState::new();
+
+        while let Some(m) = picker.next(&self.board, &self.heuristics) {
+            if m == excluded {
+                continue;
+            }
+            moves_seen += 1;
+
+            let quiet = self.board.piece_at(Square(to_square(m))).is_none() && !is_en_passant(m) && !is_promotion(m);
+
+            // Late move pruning: once enough quiets have failed, skip the rest outright
+            if quiet && moves_searched > 0 && quiets_tried.len() >= lmp_limit {
+                continue;
+            }
+
+            let nodes_before = self.nodes;
+            self.move_stack[ply] = m;
+            make_move(&mut self.board, m, &mut state);
+            let gives_check = self.board.in_check();
+
+            // Futility pruning (checking moves are always worth a look)
+            if quiet && futile && moves_searched > 0 && !gives_check {
+                undo_move(&mut self.board, m, &state);
+                continue;
+            }
+
+            if quiet {
+                quiets_tried.push(m);
+            }
+
+            let mut extension = 0;
+            if self.extensions < MAX_EXTENSIONS.min(self.root_depth) {
+                extension = self.extension(m, ply, is_pv, gives_check, previous);
+
+                // Singular: the TT move beats every alternative by a clear margin, judged by
+                // a reduced search of the other moves with the TT move excluded
+                if extension == 0
+                    && let Some(entry) = entry
+                    && self.is_singular_candidate(m, tt_move, entry, depth, excluded)
+                {
+                    undo_move(&mut self.board, m, &state);
+
+                    let singular_beta = entry.score - SE_MARGIN_PER_PLY * depth as i32;
+                    self.excluded[ply] = m;
+                    let score = self.negamax((depth - 1) / 2, ply, singular_beta - 1, singular_beta);
+                    self.excluded[ply] = 0;
+
+                    self.move_stack[ply] = m;
+                    make_move(&mut self.board, m, &mut state);
+
+                    if self.stopped {
+                        undo_move(&mut self.board, m, &state);
+                        return 0;
+                    }
+                    if score < singular_beta {
+                        extension = 1;
+                    }
+                }
+            }
+            let new_depth = depth - 1 + extension;
+            self.extensions += extension;
+
+            // PVS: full window for the first move, a null window to prove the rest are worse
+            let mut score;
+            if moves_searched == 0 {
+                score = -self.negamax(new_depth, ply + 1, -beta, -alpha);
+            } else {
+                // Late move reductions: quiet moves this far down the list rarely matter,
+                // so search them shallower and only look again if one surprises us
+                let mut reduction = 0;
+                if self.options.late_move_reductions
+                    && depth >= LMR_MIN_DEPTH
+                    && moves_searched >= LMR_MIN_MOVES
+                    && quiet
+                    && !in_check
+                    && !gives_check
+                {
+                    reduction = LMR_TABLE[(depth as usize).min(63)][moves_searched.min(63)];
+                    if is_pv {
+                        reduction = reduction.saturating_sub(1);
+                    }
+                    reduction = reduction.min(depth - 2);
+                }
+
+                score = -self.negamax(new_depth - reduction, ply + 1, -alpha - 1, -alpha);
+                if reduction > 0 && score > alpha {
+                    score = -self.negamax(new_depth, ply + 1, -alpha - 1, -alpha);
+                }
+                if score > alpha && score < beta {
+                    score = -self.negamax(new_depth, ply + 1, -beta, -alpha);
+                }
+            }
+
+            self.extensions -= extension;
+            undo_move(&mut self.board, m, &state);
+            moves_searched += 1;
+
+            if ply == 0 {
+                self.root_nodes[from_square(m) as usize * 64 + to_square(m) as usize] += self.nodes - nodes_before;
+            }
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > alpha {
+                alpha = score;
+                best_move = Some(m);
+                self.update_pv(ply, m);
+
+                if score >= beta {
+                    if quiet {
+                        self.heuristics.update(&self.board, ply, depth, m, &quiets_tried, previous);
+                    }
+
+                    if excluded == 0 {
+                        self.shared.tt.store(self.board.hash, best_move, beta, depth, Bound::Lower, ply);
+                    }
+                    return beta;
+                }
+            }
+        }
+
+        if moves_seen == 0 {
+            // With the only move excluded there is nothing to compare it against
+            if excluded != 0 {
+                return alpha;
+            }
+
+            // Checkmate is scored relative to the root so shorter mates score higher
+            return if in_check { -MATE + ply as i32 } else { 0 };
+        }
+
+        if excluded == 0 {
+            let bound = if alpha > original_alpha { Bound::Exact } else { Bound::Upper };
+            self.shared.tt.store(self.board.hash, best_move, alpha, depth, bound, ply);
+        }
+
+        alpha
+    }
+
+    // Cheap extensions for the move just made (the board is already past it)
+    fn extension(&self, m: Move, ply: usize, is_pv: bool, gives_check: bool, previous: Option<Move>) -> u32 {
+        let to = to_square(m);
+        let mover = self.board.side_to_move.opposite();
+
+        if self.options.check_extension && gives_check {
+            return 1;
+        }
+
+        // A passed pawn reaching the seventh is one step from a new queen
+        if self.options.pawn_push_extension
+            && let Some((Piece::Pawn, _)) = self.board.piece_at(Square(to))
+        {
+            let seventh = if mover == Color::White { RANK_7 } else { RANK_2 };
+            if seventh & passed_pawns(&self.board, mover) & (1u64 << to) != 0 {
+                return 1;
+            }
+        }
+
+        // Taking back on the square of the last capture keeps the exchange balanced;
+        // only on the PV, where the extra effort pays for itself
+        if self.options.recapture_extension
+            && is_pv
+            && ply > 0
+            && let Some(previous) = previous
+            && is_capture(previous)
+            && is_capture(m)
+            && to_square(previous) == to
+        {
+            return 1;
+        }
+
+        0
+    }
+
+    fn is_singular_candidate(&self, m: Move, tt_move: Option<Move>, entry: TtEntry, depth: u32, excluded: Move) -> bool {
+        self.options.singular_extension
+            && depth >= SE_MIN_DEPTH
+            && excluded == 0
+            && tt_move == Some(m)
+            && entry.best_move == Some(m)
+            && matches!(entry.bound, Bound::Lower | Bound::Exact)
+            && entry.depth as u32 + 3 >= depth
+            && entry.score.abs() < MATE_BOUND
+    }
+
+    // Null-move pruning: if passing the turn still fails high at reduced depth, a real move
+    // almost surely would too. Returns the cutoff score when the node can be pruned.
+    fn null_move(&mut self, depth: u32, ply: usize, beta: i32, static_eval: i32) -> Option<i32> {
+        let side = self.board.side_to_move as usize;
+        let pieces = [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen]
+            .iter()
+            .map(|&piece| self.board.bitboards[side][piece as usize].count_ones())
+            .sum::<u32>();
+
+        // Never two nulls in a row, and never with only pawns left (zugzwang is the rule there)
+        if !self.options.null_move
+            || depth < NMP_MIN_DEPTH
+            || ply == 0
+            || ply < self.nmp_min_ply
+            || self.move_stack[ply - 1] == 0
+            || pieces == 0
+            || static_eval < beta
+            || beta.abs() >= MATE_BOUND
+        {
+            return None;
+        }
+
+        let reduction = 3 + depth / 6;
+        let mut state = GameState::new();
+
+        self.move_stack[ply] = 0;
+        make_null_move(&mut self.board, &mut state);
+        let score = -self.negamax(depth.saturating_sub(1 + reduction), ply + 1, -beta, -beta + 1);
+        undo_null_move(&mut self.board, &state);
+
+        if self.stopped || score < beta {
+            return None;
+        }
+
+        // With few pieces left zugzwang becomes likely, so confirm the cutoff with a
+        // reduced search of our own moves, with null moves off for the next few plies
+        if pieces <= NMP_VERIFY_MAX_PIECES {
+            let verify_depth = depth.saturating_sub(reduction).max(1);
+            let saved = self.nmp_min_ply;
+            self.nmp_min_ply = ply + 3 * verify_depth as usize / 4 + 1;
+            let verified = self.negamax(verify_depth, ply, beta - 1, beta);
+            self.nmp_min_ply = saved;
+
+            if self.stopped || verified < beta {
+                return None;
+            }
+        }
+
+        // Unproven mates from a null-move search are not trusted
+        Some(beta)
+    }
+
+    // Resolve captures and promotions before trusting the static evaluation, so the
+    // horizon never falls in the middle of an exchange
+    fn quiescence(&mut self, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.pv_length[ply] = ply;
+
+        if self.nodes.is_multiple_of(CHECK_INTERVAL) && self.should_stop() {
+            self.stopped = true;
+        }
+        if self.stopped {
+            return 0;
+        }
+
+        self.nodes += 1;
+
+        let in_check = self.board.in_check();
+        let moves = generate_legal_moves(&self.board);
+
+        // Standing pat is not an option in check, so every evasion gets searched
+        if in_check && moves.is_empty() {
+            return -MATE + ply as i32;
+        }
+
+        let stand_pat = evaluate(&self.board, &mut self.pawn_table);
+        if ply >= MAX_PLY - 1 {
+            return stand_pat;
+        }
+
+        if !in_check {
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+
+        // Noisy moves only (all moves when in check), best exchanges first
+        let mut noisy: Vec<(Move, i32)> = moves
+            .iter()
+            .filter(|&&m| in_check || is_capture(m) || is_promotion(m))
+            .map(|&m| (m, see(&self.board, m)))
+            .collect();
+        noisy.sort_by_key(|&(_, exchange)| std::cmp::Reverse(exchange));
+
+        let mut best = if in_check { -INFINITY } else { stand_pat };
+        let mut state = GameState::new();
+
+        for (m, exchange) in noisy {
+            if !in_check {
+                // Losing exchanges are left for the main search to find
+                if exchange < 0 {
+                    continue;
+                }
+
+                // Delta pruning: even winning the captured piece outright won't reach alpha
+                if !is_promotion(m) && stand_pat + self.captured_value(m) + DELTA_MARGIN <= alpha {
+                    continue;
+                }
+            }
+
+            make_move(&mut self.board, m, &mut state);
+            let score = -self.quiescence(ply + 1, -beta, -alpha);
+            undo_move(&mut self.board, m, &state);
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > best {
+                best = score;
+                if score > alpha {
+                    alpha = score;
+                    if score >= beta {
+                        break;
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    fn captured_value(&self, m: Move) -> i32 {
+        if is_en_passant(m) {
+            return SEE_VALUES[Piece::Pawn as usize];
+        }
+
+        match self.board.piece_at(Square(to_square(m))) {
+            Some((piece, _)) => SEE_VALUES[piece as usize],
+            None => 0,
+        }
+    }
+
+    // Prepend `m` to the child's principal variation
+    fn update_pv(&mut self, ply: usize, m: Move) {
+        self.pv[ply][ply] = m;
+
+        let child_length = self.pv_length[ply + 1].max(ply + 1);
+        for i in ply + 1..child_length {
+            self.pv[ply][i] = self.pv[ply + 1][i];
+        }
+        self.pv_length[ply] = child_length;
+    }
+
+    // Polled every CHECK_INTERVAL nodes, which is also when the node count is published
+    fn should_stop(&self) -> bool {
+        self.publish_nodes();
+
+        if self.shared.stop.load(Ordering::Relaxed) || self.shared.abort.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        self.time.hard_limit_reached()
+    }
+
+    fn publish_nodes(&self) {
+        self.shared.nodes[self.id].store(self.nodes, Ordering::Relaxed);
+    }
+}
+
+// UCI info line for a finished iteration, with the nodes of all threads
+fn report(shared: &Shared, result: &SearchResult) {
+    let elapsed = shared.start.elapsed();
+    let nodes = shared.total_nodes();
+    let nps = (nodes as f64 / elapsed.as_secs_f64().max(1e-3)) as u64;
+    let pv: Vec<String> = result.pv.iter().map(|&m| move_to_uci(m)).collect();
+
+    println!(
+        "info depth {} score {} nodes {} nps {} hashfull {} time {} pv {}",
+        result.depth,
+        format_score(result.score),
+        nodes,
+        nps,
+        shared.tt.hashfull(),
+        elapsed.as_millis(),
+        pv.join(" ")
+    );
+}
+
+/// UCI score: centipawns, or moves to mate (negative when we are getting mated)
+pub fn format_score(score: i32) -> String {
+    if score >= MATE_BOUND {
+        format!("mate {}", (MATE - score + 1) / 2)
+    } else if score <= -MATE_BOUND {
+        format!("mate -{}", (MATE + score) / 2)
+    } else {
+        format!("cp {}", score)
+    }
+}
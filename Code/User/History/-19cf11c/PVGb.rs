@@ -0,0 +1,599 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use lazy_static::lazy_static;
+
+use crate::eval::evaluate;
+use crate::eval::pawns::PawnTable;
+use crate::movegen::generate::generate_legal_moves;
+use crate::movepick::{Heuristics, MovePicker};
+use crate::see::{see, SEE_VALUES};
+use crate::state::board::{Board, Piece, Square};
+use crate::state::make_move::make_move;
+use crate::state::null_move::{make_null_move, undo_null_move};
+use crate::state::r#move::{is_capture, is_en_passant, is_promotion, move_to_uci, to_square, Move};
+use crate::state::state::GameState;
+use crate::state::undo_move::undo_move;
+use crate::tt::{Bound, TranspositionTable};
+
+pub const INFINITY: i32 = 32_000;
+pub const MATE: i32 = 31_000;
+pub const MAX_PLY: usize = 128;
+
+/// Any score beyond this is a forced mate
+pub const MATE_BOUND: i32 = MATE - MAX_PLY as i32;
+
+// Half-width of the first aspiration window, in centipawns
+const ASPIRATION_WINDOW: i32 = 25;
+
+// Quiescence skips captures that can't lift the score this close to alpha
+const DELTA_MARGIN: i32 = 200;
+
+// Poll the clock and the stop flag every this many nodes
+const CHECK_INTERVAL: u64 = 2048;
+
+// Reverse futility: a static eval this far above beta per ply of depth is trusted to hold
+const RFP_MAX_DEPTH: u32 = 6;
+const RFP_MARGIN: i32 = 80;
+
+// Null-move pruning: minimum depth, and how few pieces make zugzwang worth verifying
+const NMP_MIN_DEPTH: u32 = 3;
+const NMP_VERIFY_MAX_PIECES: u32 = 2;
+
+// Futility pruning: quiet moves can't make up this much at the last few plies
+const FUTILITY_MARGINS: [i32; 4] = [0, 100, 200, 300];
+
+// Late move pruning: quiet moves searched at depth d before the rest are skipped
+const LMP_MAX_DEPTH: u32 = 3;
+const LMP_BASE: usize = 3;
+
+// Late move reductions apply from this depth and this many moves in
+const LMR_MIN_DEPTH: u32 = 3;
+const LMR_MIN_MOVES: usize = 3;
+
+lazy_static! {
+    // Late move reductions by [depth][moves searched], growing with the log of both
+    static ref LMR_TABLE: [[u32; 64]; 64] = {
+        let mut table = [[0; 64]; 64];
+        for (depth, row) in table.iter_mut().enumerate().skip(1) {
+            for (moves, reduction) in row.iter_mut().enumerate().skip(1) {
+                *reduction = (0.75 + (depth as f64).ln() * (moves as f64).ln() / 2.25) as u32;
+            }
+        }
+        table
+    };
+}
+
+/// When the search has to stop
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SearchLimits {
+    pub depth: Option<u32>,
+    pub deadline: Option<Instant>,
+}
+
+/// Pruning and reduction techniques that can be switched off (UCI options), for A/B testing
+#[derive(Clone, Copy, Debug)]
+pub struct SearchOptions {
+    pub null_move: bool,
+    pub late_move_reductions: bool,
+    pub reverse_futility: bool,
+    pub futility: bool,
+    pub late_move_pruning: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            null_move: true,
+            late_move_reductions: true,
+            reverse_futility: true,
+            futility: true,
+            late_move_pruning: true,
+        }
+    }
+}
+
+/// Outcome of a completed search
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u32,
+    pub nodes: u64,
+    pub pv: Vec<Move>,
+}
+
+struct Searcher<'a> {
+    board: Board,
+    limits: SearchLimits,
+    options: SearchOptions,
+    stop: &'a AtomicBool,
+    tt: &'a TranspositionTable,
+    pawn_table: PawnTable,
+    start: Instant,
+    nodes: u64,
+    stopped: bool,
+
+    // Triangular PV table: pv[ply] holds the line from ply onward
+    pv: Vec<[Move; MAX_PLY]>,
+    pv_length: [usize; MAX_PLY],
+
+    // Principal variation of the last completed iteration, searched first
+    previous_pv: Vec<Move>,
+
+    // Move ordering state, and the move played at each ply (for countermoves)
+    heuristics: Heuristics,
+    move_stack: [Move; MAX_PLY],
+
+    // Null moves stay off below this ply while a null-move cutoff is being verified
+    nmp_min_ply: usize,
+}
+
+/// Iterative deepening negamax search from `board` until a limit is hit or `stop` is set
+pub fn search(
+    board: &Board,
+    limits: SearchLimits,
+    options: SearchOptions,
+    stop: &AtomicBool,
+    tt: &TranspositionTable,
+) -> SearchResult {
+    tt.new_search();
+
+    let mut searcher = Searcher {
+        board: board.clone(),
+        limits,
+        options,
+        stop,
+        tt,
+        pawn_table: PawnTable::new(),
+        start: Instant::now(),
+        nodes: 0,
+        stopped: false,
+        pv: vec![[0; MAX_PLY]; MAX_PLY],
+        pv_length: [0; MAX_PLY],
+        previous_pv: Vec::new(),
+        heuristics: Heuristics::new(),
+        move_stack: [0; MAX_PLY],
+        nmp_min_ply: 0,
+    };
+
+    // Always have something to play, even if the first iteration is cut short
+    let root_moves = generate_legal_moves(board);
+    let mut result = SearchResult {
+        best_move: root_moves.iter().next().copied(),
+        score: 0,
+        depth: 0,
+        nodes: 0,
+        pv: Vec::new(),
+    };
+
+    if root_moves.is_empty() {
+        return result;
+    }
+
+    let max_depth = limits.depth.unwrap_or(MAX_PLY as u32 - 1).min(MAX_PLY as u32 - 1);
+
+    for depth in 1..=max_depth {
+        let score = searcher.aspiration(depth, result.score);
+
+        // A partial iteration can't be trusted; keep the last complete one
+        if searcher.stopped {
+            break;
+        }
+
+        result.score = score;
+        result.depth = depth;
+        result.nodes = searcher.nodes;
+        result.pv = searcher.pv[0][..searcher.pv_length[0]].to_vec();
+        result.best_move = result.pv.first().copied().or(result.best_move);
+        searcher.previous_pv = result.pv.clone();
+
+        searcher.report(&result);
+
+        // No point searching deeper once a forced mate has been found
+        if score.abs() >= MATE_BOUND && depth as i32 >= MATE - score.abs() {
+            break;
+        }
+    }
+
+    result.nodes = searcher.nodes;
+    result
+}
+
+impl Searcher<'_> {
+    // Search with a narrow window around the previous score, widening on failure
+    fn aspiration(&mut self, depth: u32, previous: i32) -> i32 {
+        if depth < 4 {
+            return self.negamax(depth, 0, -INFINITY, INFINITY);
+        }
+
+        let mut delta = ASPIRATION_WINDOW;
+        let mut alpha = (previous - delta).max(-INFINITY);
+        let mut beta = (previous + delta).min(INFINITY);
+
+        loop {
+            let score = self.negamax(depth, 0, alpha, beta);
+            if self.stopped {
+                return score;
+            }
+
+            if score <= alpha {
+                alpha = (score - delta).max(-INFINITY);
+            } else if score >= beta {
+                beta = (score + delta).min(INFINITY);
+            } else {
+                return score;
+            }
+
+            delta *= 2;
+        }
+    }
+
+    fn negamax(&mut self, depth: u32, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.pv_length[ply] = ply;
+
+        if self.nodes.is_multiple_of(CHECK_INTERVAL) && self.should_stop() {
+            self.stopped = true;
+        }
+        if self.stopped {
+            return 0;
+        }
+
+        self.nodes += 1;
+
+        if depth == 0 || ply >= MAX_PLY - 1 {
+            return self.quiescence(ply, alpha, beta);
+        }
+
+        // Cut off on a deep enough stored bound, except in PV nodes where we want the full line
+        let is_pv = beta - alpha > 1;
+        let entry = self.tt.probe(self.board.hash, ply);
+        if let Some(entry) = entry
+            && !is_pv
+            && entry.depth as u32 >= depth
+        {
+            match entry.bound {
+                Bound::Exact => return entry.score,
+                Bound::Lower if entry.score >= beta => return entry.score,
+                Bound::Upper if entry.score <= alpha => return entry.score,
+                _ => {}
+            }
+        }
+
+        let in_check = self.board.in_check();
+        let static_eval = if in_check {
+            -INFINITY
+        } else {
+            evaluate(&self.board, &mut self.pawn_table)
+        };
+
+        // Node-level pruning, only where a wrong guess can't corrupt the principal variation
+        if !is_pv && !in_check {
+            // Reverse futility: so far above beta that a quiet reply can't bring it back
+            if self.options.reverse_futility
+                && depth <= RFP_MAX_DEPTH
+                && beta.abs() < MATE_BOUND
+                && static_eval - RFP_MARGIN * depth as i32 >= beta
+            {
+                return beta;
+            }
+
+            if let Some(score) = self.null_move(depth, ply, beta, static_eval) {
+                return score;
+            }
+        }
+
+        // Try the stored best move first, falling back to the previous principal variation
+        let tt_move = entry
+            .and_then(|entry| entry.best_move)
+            .or_else(|| self.previous_pv.get(ply).copied());
+        let previous = if ply > 0 && self.move_stack[ply - 1] != 0 {
+            Some(self.move_stack[ply - 1])
+        } else {
+            None
+        };
+        let mut picker = MovePicker::new(&self.board, &self.heuristics, ply, tt_move, previous);
+
+        // Quiet moves at the last plies that can't realistically raise alpha
+        let futile = self.options.futility
+            && !is_pv
+            && !in_check
+            && (depth as usize) < FUTILITY_MARGINS.len()
+            && alpha.abs() < MATE_BOUND
+            && static_eval + FUTILITY_MARGINS[depth as usize] <= alpha;
+        let lmp_limit = if self.options.late_move_pruning && !is_pv && !in_check && depth <= LMP_MAX_DEPTH {
+            LMP_BASE + (depth * depth) as usize
+        } else {
+            usize::MAX
+        };
+
+        let original_alpha = alpha;
+        let mut best_move = None;
+        let mut moves_seen = 0;
+        let mut moves_searched = 0;
+        let mut quiets_tried = Vec::new();
+        let mut state = GameState::new();
+
+        while let Some(m) = picker.next(&self.board, &self.heuristics) {
+            moves_seen += 1;
+
+            let quiet = self.board.piece_at(Square(to_square(m))).is_none() && !is_en_passant(m) && !is_promotion(m);
+
+            // Late move pruning: once enough quiets have failed, skip the rest outright
+            if quiet && moves_searched > 0 && quiets_tried.len() >= lmp_limit {
+                continue;
+            }
+
+            self.move_stack[ply] = m;
+            make_move(&mut self.board, m, &mut state);
+            let gives_check = self.board.in_check();
+
+            // Futility pruning (checking moves are always worth a look)
+            if quiet && futile && moves_searched > 0 && !gives_check {
+                undo_move(&mut self.board, m, &state);
+                continue;
+            }
+
+            if quiet {
+                quiets_tried.push(m);
+            }
+
+            // PVS: full window for the first move, a null window to prove the rest are worse
+            let mut score;
+            if moves_searched == 0 {
+                score = -self.negamax(depth - 1, ply + 1, -beta, -alpha);
+            } else {
+                // Late move reductions: quiet moves this far down the list rarely matter,
+                // so search them shallower and only look again if one surprises us
+                let mut reduction = 0;
+                if self.options.late_move_reductions
+                    && depth >= LMR_MIN_DEPTH
+                    && moves_searched >= LMR_MIN_MOVES
+                    && quiet
+                    && !in_check
+                    && !gives_check
+                {
+                    reduction = LMR_TABLE[(depth as usize).min(63)][moves_searched.min(63)];
+                    if is_pv {
+                        reduction = reduction.saturating_sub(1);
+                    }
+                    reduction = reduction.min(depth - 2);
+                }
+
+                score = -self.negamax(depth - 1 - reduction, ply + 1, -alpha - 1, -alpha);
+                if reduction > 0 && score > alpha {
+                    score = -self.negamax(depth - 1, ply + 1, -alpha - 1, -alpha);
+                }
+                if score > alpha && score < beta {
+                    score = -self.negamax(depth - 1, ply + 1, -beta, -alpha);
+                }
+            }
+
+            undo_move(&mut self.board, m, &state);
+            moves_searched += 1;
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > alpha {
+                alpha = score;
+                best_move = Some(m);
+                self.update_pv(ply, m);
+
+                if score >= beta {
+                    if quiet {
+                        self.heuristics.update(&self.board, ply, depth, m, &quiets_tried, previous);
+                    }
+
+                    self.tt.store(self.board.hash, best_move, beta, depth, Bound::Lower, ply);
+                    return beta;
+                }
+            }
+        }
+
+        if moves_seen == 0 {
+            // Checkmate is scored relative to the root so shorter mates score higher
+            return if in_check { -MATE + ply as i32 } else { 0 };
+        }
+
+        let bound = if alpha > original_alpha { Bound::Exact } else { Bound::Upper };
+        self.tt.store(self.board.hash, best_move, alpha, depth, bound, ply);
+
+        alpha
+    }
+
+    // Null-move pruning: if passing the turn still fails high at reduced depth, a real move
+    // almost surely would too. Returns the cutoff score when the node can be pruned.
+    fn null_move(&mut self, depth: u32, ply: usize, beta: i32, static_eval: i32) -> Option<i32> {
+        let side = self.board.side_to_move as usize;
+        let pieces = [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen]
+            .iter()
+            .map(|&piece| self.board.bitboards[side][piece as usize].count_ones())
+            .sum::<u32>();
+
+        // Never two nulls in a row, and never with only pawns left (zugzwang is the rule there)
+        if !self.options.null_move
+            || depth < NMP_MIN_DEPTH
+            || ply == 0
+            || ply < self.nmp_min_ply
+            || self.move_stack[ply - 1] == 0
+            || pieces == 0
+            || static_eval < beta
+            || beta.abs() >= MATE_BOUND
+        {
+            return None;
+        }
+
+        let reduction = 3 + depth / 6;
+        let mut state = GameState::new();
+
+        self.move_stack[ply] = 0;
+        make_null_move(&mut self.board, &mut state);
+        let score = -self.negamax(depth.saturating_sub(1 + reduction), ply + 1, -beta, -beta + 1);
+        undo_null_move(&mut self.board, &state);
+
+        if self.stopped || score < beta {
+            return None;
+        }
+
+        // With few pieces left zugzwang becomes likely, so confirm the cutoff with a
+        // reduced search of our own moves, with null moves off for the next few plies
+        if pieces <= NMP_VERIFY_MAX_PIECES {
+            let verify_depth = depth.saturating_sub(reduction).max(1);
+            let saved = self.nmp_min_ply;
+            self.nmp_min_ply = ply + 3 * verify_depth as usize / 4 + 1;
+            let verified = self.negamax(verify_depth, ply, beta - 1, beta);
+            self.nmp_min_ply = saved;
+
+            if self.stopped || verified < beta {
+                return None;
+            }
+        }
+
+        // Unproven mates from a null-move search are not trusted
+        Some(beta)
+    }
+
+    // Resolve captures and promotions before trusting the static evaluation, so the
+    // horizon never falls in the middle of an exchange
+    fn quiescence(&mut self, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.pv_length[ply] = ply;
+
+        if self.nodes.is_multiple_of(CHECK_INTERVAL) && self.should_stop() {
+            self.stopped = true;
+        }
+        if self.stopped {
+            return 0;
+        }
+
+        self.nodes += 1;
+
+        let in_check = self.board.in_check();
+        let moves = generate_legal_moves(&self.board);
+
+        // Standing pat is not an option in check, so every evasion gets searched
+        if in_check && moves.is_empty() {
+            return -MATE + ply as i32;
+        }
+
+        let stand_pat = evaluate(&self.board, &mut self.pawn_table);
+        if ply >= MAX_PLY - 1 {
+            return stand_pat;
+        }
+
+        if !in_check {
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+
+        // Noisy moves only (all moves when in check), best exchanges first
+        let mut noisy: Vec<(Move, i32)> = moves
+            .iter()
+            .filter(|&&m| in_check || is_capture(m) || is_promotion(m))
+            .map(|&m| (m, see(&self.board, m)))
+            .collect();
+        noisy.sort_by_key(|&(_, exchange)| std::cmp::Reverse(exchange));
+
+        let mut best = if in_check { -INFINITY } else { stand_pat };
+        let mut state = GameState::new();
+
+        for (m, exchange) in noisy {
+            if !in_check {
+                // Losing exchanges are left for the main search to find
+                if exchange < 0 {
+                    continue;
+                }
+
+                // Delta pruning: even winning the captured piece outright won't reach alpha
+                if !is_promotion(m) && stand_pat + self.captured_value(m) + DELTA_MARGIN <= alpha {
+                    continue;
+                }
+            }
+
+            make_move(&mut self.board, m, &mut state);
+            let score = -self.quiescence(ply + 1, -beta, -alpha);
+            undo_move(&mut self.board, m, &state);
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > best {
+                best = score;
+                if score > alpha {
+                    alpha = score;
+                    if score >= beta {
+                        break;
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    fn captured_value(&self, m: Move) -> i32 {
+        if is_en_passant(m) {
+            return SEE_VALUES[Piece::Pawn as usize];
+        }
+
+        match self.board.piece_at(Square(to_square(m))) {
+            Some((piece, _)) => SEE_VALUES[piece as usize],
+            None => 0,
+        }
+    }
+
+    // Prepend `m` to the child's principal variation
+    fn update_pv(&mut self, ply: usize, m: Move) {
+        self.pv[ply][ply] = m;
+
+        let child_length = self.pv_length[ply + 1].max(ply + 1);
+        for i in ply + 1..child_length {
+            self.pv[ply][i] = self.pv[ply + 1][i];
+        }
+        self.pv_length[ply] = child_length;
+    }
+
+    fn should_stop(&self) -> bool {
+        if self.stop.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        match self.limits.deadline {
+            Some(deadline) => Instant::now() >= deadline,
+            None => false,
+        }
+    }
+
+    // UCI info line for a finished iteration
+    fn report(&self, result: &SearchResult) {
+        let elapsed = self.start.elapsed();
+        let nps = (self.nodes as f64 / elapsed.as_secs_f64().max(1e-3)) as u64;
+        let pv: Vec<String> = result.pv.iter().map(|&m| move_to_uci(m)).collect();
+
+        println!(
+            "info depth {} score {} nodes {} nps {} hashfull {} time {} pv {}",
+            result.depth,
+            format_score(result.score),
+            self.nodes,
+            nps,
+            self.tt.hashfull(),
+            elapsed.as_millis(),
+            pv.join(" ")
+        );
+    }
+}
+
+/// UCI score: centipawns, or moves to mate (negative when we are getting mated)
+pub fn format_score(score: i32) -> String {
+    if score >= MATE_BOUND {
+        format!("mate {}", (MATE - score + 1) / 2)
+    } else if score <= -MATE_BOUND {
+        format!("mate -{}", (MATE + score) / 2)
+    } else {
+        format!("cp {}", score)
+    }
+}
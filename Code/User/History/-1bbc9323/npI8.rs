@@ -0,0 +1,206 @@
+pub mod pst;
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::movegen::kings::KING_ATTACKS;
+use crate::movegen::knights;
+use crate::movegen::pawns::{Bitboard, A_FILE, H_FILE};
+use crate::movegen::queens::{self, BISHOP_MAGICS_TABLE};
+use crate::movegen::rooks::ROOK_MAGICS_TABLE;
+use crate::state::board::{Board, Color, Piece};
+
+/// A middlegame / endgame pair of centipawn values, blended by game phase at the end
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct Score {
+    pub mg: i32,
+    pub eg: i32,
+}
+
+impl Score {
+    pub const fn new(mg: i32, eg: i32) -> Self {
+        Self { mg, eg }
+    }
+}
+
+impl Add for Score {
+    type Output = Score;
+
+    fn add(self, other: Score) -> Score {
+        Score::new(self.mg + other.mg, self.eg + other.eg)
+    }
+}
+
+impl Sub for Score {
+    type Output = Score;
+
+    fn sub(self, other: Score) -> Score {
+        Score::new(self.mg - other.mg, self.eg - other.eg)
+    }
+}
+
+impl Neg for Score {
+    type Output = Score;
+
+    fn neg(self) -> Score {
+        Score::new(-self.mg, -self.eg)
+    }
+}
+
+impl Mul<i32> for Score {
+    type Output = Score;
+
+    fn mul(self, n: i32) -> Score {
+        Score::new(self.mg * n, self.eg * n)
+    }
+}
+
+impl AddAssign for Score {
+    fn add_assign(&mut self, other: Score) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Score {
+    fn sub_assign(&mut self, other: Score) {
+        *self = *self - other;
+    }
+}
+
+/// Phase of a position with all minor and major pieces still on the board
+pub const MAX_PHASE: i32 = 24;
+
+// How much each piece type counts towards the game phase
+const PHASE_WEIGHTS: [i32; 6] = [0, 1, 1, 2, 4, 0];
+
+// Per safe square reachable, relative to a typical count for the piece
+const KNIGHT_MOBILITY: (Score, i32) = (Score::new(4, 4), 4);
+const BISHOP_MOBILITY: (Score, i32) = (Score::new(5, 5), 7);
+const ROOK_MOBILITY: (Score, i32) = (Score::new(2, 4), 7);
+const QUEEN_MOBILITY: (Score, i32) = (Score::new(1, 2), 14);
+
+const BISHOP_PAIR: Score = Score::new(30, 50);
+const ROOK_OPEN_FILE: Score = Score::new(40, 20);
+const ROOK_SEMI_OPEN_FILE: Score = Score::new(20, 10);
+
+// Attack units a piece adds when it hits the squares around the enemy king
+const KING_ATTACK_WEIGHTS: [i32; 6] = [0, 2, 2, 3, 5, 0];
+const MAX_KING_DANGER: i32 = 500;
+
+/// Static evaluation in centipawns from the side to move's point of view
+pub fn evaluate(board: &Board) -> i32 {
+    let score = material_pst(board, Color::White) - material_pst(board, Color::Black)
+        + pieces(board, Color::White)
+        - pieces(board, Color::Black);
+
+    let phase = game_phase(board);
+    let blended = (score.mg * phase + score.eg * (MAX_PHASE - phase)) / MAX_PHASE;
+
+    match board.side_to_move {
+        Color::White => blended,
+        Color::Black => -blended,
+    }
+}
+
+/// 0 (pawns and kings only) up to MAX_PHASE (full set of pieces), from the piece counts
+pub fn game_phase(board: &Board) -> i32 {
+    let mut phase = 0;
+
+    for piece in [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen] {
+        let count = board.bitboards[Color::White as usize][piece as usize].count_ones()
+            + board.bitboards[Color::Black as usize][piece as usize].count_ones();
+        phase += count as i32 * PHASE_WEIGHTS[piece as usize];
+    }
+
+    // Early promotions can push the count past a full set
+    phase.min(MAX_PHASE)
+}
+
+/// Material and piece-square total of one side, summed from scratch
+pub fn material_pst(board: &Board, color: Color) -> Score {
+    let mut score = Score::default();
+
+    for piece in [Piece::Pawn, Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen, Piece::King] {
+        let mut bb = board.bitboards[color as usize][piece as usize];
+        while bb != 0 {
+            let sq = bb.trailing_zeros() as u8;
+            score += pst::value(piece, color, sq);
+            bb &= bb - 1;
+        }
+    }
+
+    score
+}
+
+/// Squares attacked by the pawns of `color`
+pub fn pawn_attacks(pawns: Bitboard, color: Color) -> Bitboard {
+    match color {
+        Color::White => ((pawns & !A_FILE) << 7) | ((pawns & !H_FILE) << 9),
+        Color::Black => ((pawns & !A_FILE) >> 9) | ((pawns & !H_FILE) >> 7),
+    }
+}
+
+// Mobility, bishop pair, rooks on open files and pressure on the enemy king for `color`
+fn pieces(board: &Board, color: Color) -> Score {
+    let us = &board.bitboards[color as usize];
+    let them = &board.bitboards[color.opposite() as usize];
+    let occupied = board.occupancies[2];
+
+    // Squares worth counting: not blocked by our own men, not covered by an enemy pawn
+    let safe = !board.occupancies[color as usize] & !pawn_attacks(them[Piece::Pawn as usize], color.opposite());
+
+    let their_king = them[Piece::King as usize];
+    let king_zone = if their_king != 0 {
+        KING_ATTACKS[their_king.trailing_zeros() as usize] | their_king
+    } else {
+        0
+    };
+
+    let mut score = Score::default();
+    let mut king_attackers = 0;
+    let mut king_attack_units = 0;
+
+    for piece in [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen] {
+        let mut bb = us[piece as usize];
+
+        while bb != 0 {
+            let sq = bb.trailing_zeros() as usize;
+
+            let (attacks, (weight, typical)) = match piece {
+                Piece::Knight => (knights::ATTACKING[sq], KNIGHT_MOBILITY),
+                Piece::Bishop => (BISHOP_MAGICS_TABLE[sq].get_attacks(occupied), BISHOP_MOBILITY),
+                Piece::Rook => (ROOK_MAGICS_TABLE[sq].attacks(occupied), ROOK_MOBILITY),
+                _ => (queens::queen_moves(1u64 << sq, occupied), QUEEN_MOBILITY),
+            };
+
+            score += weight * ((attacks & safe).count_ones() as i32 - typical);
+
+            if attacks & king_zone != 0 {
+                king_attackers += 1;
+                king_attack_units += KING_ATTACK_WEIGHTS[piece as usize];
+            }
+
+            if piece == Piece::Rook {
+                let file = A_FILE << (sq % 8);
+                if file & (us[Piece::Pawn as usize] | them[Piece::Pawn as usize]) == 0 {
+                    score += ROOK_OPEN_FILE;
+                } else if file & us[Piece::Pawn as usize] == 0 {
+                    score += ROOK_SEMI_OPEN_FILE;
+                }
+            }
+
+            bb &= bb - 1;
+        }
+    }
+
+    if us[Piece::Bishop as usize].count_ones() >= 2 {
+        score += BISHOP_PAIR;
+    }
+
+    // A lone attacker is rarely dangerous; pile-ups grow quadratically
+    if king_attackers >= 2 {
+        let danger = (king_attack_units * king_attack_units).min(MAX_KING_DANGER);
+        score += Score::new(danger, 0);
+    }
+
+    score
+}
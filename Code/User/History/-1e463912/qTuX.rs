@@ -0,0 +1,87 @@
+use crate::state::board::{Board, Color, Piece, Square};
+use crate::state::r#move::{
+    from_square, is_castling, is_en_passant, move_flag, promo_piece, to_square, Move,
+    FLAG_DOUBLE_PAWN_PUSH, PROMO_B, PROMO_N, PROMO_Q, PROMO_R,
+};
+use crate::state::state::GameState;
+
+pub fn make_move(board: &mut Board, mov: Move, state: &mut GameState) -> bool {
+    let from = Square(from_square(mov));
+    let to = Square(to_square(mov));
+    let moving_piece = board.piece_at(from);
+
+    if moving_piece.is_none() {
+        return false;
+    }
+
+    let (piece, color) = moving_piece.unwrap();
+
+    // Store current board state for undo
+    state.save(board, mov);
+
+    // Clear the source square
+    board.set_piece(from, None);
+
+    // Handle captures
+    if let Some(captured) = board.piece_at(to) {
+        state.captured_piece = Some(captured);
+    }
+
+    // Handle promotions
+    let promotion = match promo_piece(mov) {
+        PROMO_N => Some(Piece::Knight),
+        PROMO_B => Some(Piece::Bishop),
+        PROMO_R => Some(Piece::Rook),
+        PROMO_Q => Some(Piece::Queen),
+        _ => None,
+    };
+    if let Some(promoted_piece) = promotion {
+        board.set_piece(to, Some((promoted_piece, color)));
+    } else {
+        board.set_piece(to, Some((piece, color)));
+    }
+
+    // Handle en passant
+    if is_en_passant(mov) {
+        let ep_capture_sq = if color == Color::White {
+            Square(to.0 - 8)
+        } else {
+            Square(to.0 + 8)
+        };
+        state.captured_piece = board.piece_at(ep_capture_sq);
+        board.set_piece(ep_capture_sq, None);
+    }
+
+    // Handle castling (a1 = 0, so white castles on the first rank)
+    if is_castling(mov) {
+        match to.0 {
+            6 => { // White kingside
+                board.set_piece(Square(7), None);
+                board.set_piece(Square(5), Some((Piece::Rook, Color::White)));
+            }
+            2 => { // White queenside
+                board.set_piece(Square(0), None);
+                board.set_piece(Square(3), Some((Piece::Rook, Color::White)));
+            }
+            62 => { // Black kingside
+                board.set_piece(Square(63), None);
+                board.set_piece(Square(61), Some((Piece::Rook, Color::Black)));
+            }
+            58 => { // Black queenside
+                board.set_piece(Square(56), None);
+                board.set_piece(Square(59), Some((Piece::Rook, Color::Black)));
+            }
+            _ => {}
+        }
+    }
+
+    // Update castling rights, en passant, etc.
+    let is_capture = state.captured_piece.is_some();
+    let is_double_push = move_flag(mov) == FLAG_DOUBLE_PAWN_PUSH;
+    board.update_state_after_move(from, to, piece, is_capture, is_double_push);
+
+    // Switch sides
+    board.side_to_move = board.side_to_move.opposite();
+
+    true
+}
@@ -0,0 +1,342 @@
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::eval::evaluate;
+use crate::eval::pawns::PawnTable;
+use crate::movegen::generate::generate_legal_moves;
+use crate::search::{search, SearchLimits, SearchOptions};
+use crate::state::board::{Board, Color, START_FEN};
+use crate::state::make_move::make_move;
+use crate::state::r#move::{move_to_string, move_to_uci, square_to_coord, Move};
+use crate::state::state::GameState;
+use crate::time::{self, Clock, DEFAULT_MOVE_OVERHEAD, MAX_MOVE_OVERHEAD};
+use crate::tt::{TranspositionTable, DEFAULT_HASH_MB, MAX_HASH_MB};
+
+const ENGINE_NAME: &str = "chess-engine";
+const ENGINE_AUTHOR: &str = "Alan Mitchell";
+
+/// Parameters of a `go` command; anything not given stays None / false
+#[derive(Clone, Default, Debug)]
+pub struct GoParams {
+    pub depth: Option<u32>,
+    pub movetime: Option<u64>,
+    pub wtime: Option<u64>,
+    pub btime: Option<u64>,
+    pub winc: Option<u64>,
+    pub binc: Option<u64>,
+    pub movestogo: Option<u32>,
+    pub infinite: bool,
+}
+
+/// UCI session: current position plus the search running in the background (if any)
+pub struct Uci {
+    board: Board,
+    tt: Arc<TranspositionTable>,
+    options: SearchOptions,
+    move_overhead: u64,
+    stop: Arc<AtomicBool>,
+    search: Option<JoinHandle<()>>,
+}
+
+impl Uci {
+    pub fn new() -> Self {
+        Self {
+            board: Board::from_fen(START_FEN).expect("start position is valid"),
+            tt: Arc::new(TranspositionTable::new(DEFAULT_HASH_MB)),
+            options: SearchOptions::default(),
+            move_overhead: DEFAULT_MOVE_OVERHEAD,
+            stop: Arc::new(AtomicBool::new(false)),
+            search: None,
+        }
+    }
+
+    /// Read commands from stdin until `quit` or EOF
+    pub fn run(&mut self) {
+        let stdin = io::stdin();
+
+        for line in stdin.lock().lines() {
+            let Ok(line) = line else { break };
+
+            if !self.handle_command(line.trim()) {
+                break;
+            }
+        }
+
+        self.stop_search();
+    }
+
+    /// Handle one command line, returning false once the engine should exit
+    pub fn handle_command(&mut self, line: &str) -> bool {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        match tokens.first().copied() {
+            Some("uci") => {
+                send(&format!("id name {}", ENGINE_NAME));
+                send(&format!("id author {}", ENGINE_AUTHOR));
+                send(&format!(
+                    "option name Hash type spin default {} min 1 max {}",
+                    DEFAULT_HASH_MB, MAX_HASH_MB
+                ));
+                send("option name Clear Hash type button");
+                send(&format!(
+                    "option name Move Overhead type spin default {} min 0 max {}",
+                    DEFAULT_MOVE_OVERHEAD, MAX_MOVE_OVERHEAD
+                ));
+                for (name, default) in self.toggles() {
+                    send(&format!("option name {} type check default {}", name, default));
+                }
+                send("uciok");
+            }
+            Some("isready") => send("readyok"),
+            Some("ucinewgame") => {
+                self.stop_search();
+                self.board = Board::from_fen(START_FEN).expect("start position is valid");
+                self.tt.clear();
+            }
+            Some("position") => {
+                self.stop_search();
+                self.set_position(&tokens[1..]);
+            }
+            Some("go") => {
+                self.stop_search();
+                self.go(parse_go(&tokens[1..]));
+            }
+            Some("stop") => self.stop_search(),
+            Some("setoption") => self.set_option(&tokens[1..]),
+            Some("quit") => return false,
+
+            // Non-standard debugging helpers
+            Some("d") => {
+                self.board.print();
+                println!("Fen: {}", self.board.to_fen());
+                println!("Key: {:016X}", self.board.hash);
+                println!("Eval: {}", evaluate(&self.board, &mut PawnTable::new()));
+
+                let mut checkers = Vec::new();
+                let mut bb = self.board.checkers;
+                while bb != 0 {
+                    checkers.push(square_to_coord(bb.trailing_zeros() as u8));
+                    bb &= bb - 1;
+                }
+                println!("Checkers: {}", checkers.join(" "));
+            }
+            Some(_) => eprintln!("Unknown command: {}", line),
+            None => {}
+        }
+
+        true
+    }
+
+    // position [startpos | fen <fen>] [moves <m1> <m2> ...]
+    fn set_position(&mut self, args: &[&str]) {
+        let moves_at = args.iter().position(|&t| t == "moves").unwrap_or(args.len());
+
+        let board = match args.first().copied() {
+            Some("startpos") => Board::from_fen(START_FEN),
+            Some("fen") => Board::from_fen(&args[1..moves_at].join(" ")),
+            _ => {
+                eprintln!("Malformed position command");
+                return;
+            }
+        };
+
+        let mut board = match board {
+            Ok(board) => board,
+            Err(e) => {
+                eprintln!("Invalid fen: {}", e);
+                return;
+            }
+        };
+
+        let mut state = GameState::new();
+        for text in args.iter().skip(moves_at + 1) {
+            match parse_move(&board, text) {
+                Some(m) => {
+                    make_move(&mut board, m, &mut state);
+                }
+                None => {
+                    eprintln!("Illegal move: {}", text);
+                    break;
+                }
+            }
+        }
+
+        self.board = board;
+    }
+
+    // setoption name <id> [value <x>]
+    fn set_option(&mut self, args: &[&str]) {
+        let value_at = args.iter().position(|&t| t == "value").unwrap_or(args.len());
+        let name = args.get(1..value_at).map(|n| n.join(" ")).unwrap_or_default();
+        let value = args.get(value_at + 1..).map(|v| v.join(" ")).unwrap_or_default();
+
+        // Options may only change while no search is using them
+        self.stop_search();
+
+        match name.to_lowercase().as_str() {
+            "hash" => match value.parse::<usize>() {
+                Ok(mb) => self.tt = Arc::new(TranspositionTable::new(mb)),
+                Err(_) => eprintln!("Invalid Hash value: {}", value),
+            },
+            "clear hash" => self.tt.clear(),
+            "move overhead" => match value.parse::<u64>() {
+                Ok(ms) => self.move_overhead = ms.min(MAX_MOVE_OVERHEAD),
+                Err(_) => eprintln!("Invalid Move Overhead value: {}", value),
+            },
+            "nullmove" | "lmr" | "reversefutility" | "futility" | "latemovepruning" | "checkextension"
+            | "singularextension" | "pawnpushextension" | "recaptureextension" => {
+                let enabled = match value.to_lowercase().as_str() {
+                    "true" => true,
+                    "false" => false,
+                    _ => {
+                        eprintln!("Invalid {} value: {}", name, value);
+                        return;
+                    }
+                };
+
+                let toggle = match name.to_lowercase().as_str() {
+                    "nullmove" => &mut self.options.null_move,
+                    "lmr" => &mut self.options.late_move_reductions,
+                    "reversefutility" => &mut self.options.reverse_futility,
+                    "futility" => &mut self.options.futility,
+                    "latemovepruning" => &mut self.options.late_move_pruning,
+                    "checkextension" => &mut self.options.check_extension,
+                    "singularextension" => &mut self.options.singular_extension,
+                    "pawnpushextension" => &mut self.options.pawn_push_extension,
+                    _ => &mut self.options.recapture_extension,
+                };
+                *toggle = enabled;
+            }
+            _ => eprintln!("Unknown option: {} = {}", name, value),
+        }
+    }
+
+    // On/off switches for the search's pruning and extension techniques, as UCI check options
+    fn toggles(&self) -> [(&'static str, bool); 9] {
+        [
+            ("NullMove", self.options.null_move),
+            ("LMR", self.options.late_move_reductions),
+            ("ReverseFutility", self.options.reverse_futility),
+            ("Futility", self.options.futility),
+            ("LateMovePruning", self.options.late_move_pruning),
+            ("CheckExtension", self.options.check_extension),
+            ("SingularExtension", self.options.singular_extension),
+            ("PawnPushExtension", self.options.pawn_push_extension),
+            ("RecaptureExtension", self.options.recapture_extension),
+        ]
+    }
+
+    fn go(&mut self, params: GoParams) {
+        self.stop.store(false, Ordering::SeqCst);
+
+        let board = self.board.clone();
+        let stop = Arc::clone(&self.stop);
+        let tt = Arc::clone(&self.tt);
+        let options = self.options;
+        let limits = search_limits(&params, board.side_to_move, self.move_overhead);
+
+        self.search = Some(thread::spawn(move || {
+            let result = search(&board, limits, options, &stop, &tt);
+
+            // In infinite mode bestmove may only be sent after the GUI says stop
+            if params.infinite {
+                while !stop.load(Ordering::SeqCst) {
+                    thread::sleep(Duration::from_millis(1));
+                }
+            }
+
+            match result.best_move {
+                Some(m) => send(&format!("bestmove {}", move_to_uci(m))),
+                None => send("bestmove 0000"),
+            }
+        }));
+    }
+
+    /// Signal the search thread and wait for it to print its bestmove
+    fn stop_search(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+
+        if let Some(handle) = self.search.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+// Turn the go parameters into a depth limit and time limits for the side to move
+fn search_limits(params: &GoParams, side: Color, move_overhead: u64) -> SearchLimits {
+    let mut limits = SearchLimits {
+        depth: params.depth,
+        time: None,
+    };
+
+    if params.infinite {
+        return limits;
+    }
+
+    let (time, inc) = match side {
+        Color::White => (params.wtime, params.winc),
+        Color::Black => (params.btime, params.binc),
+    };
+
+    let clock = Clock {
+        time,
+        inc: inc.unwrap_or(0),
+        movestogo: params.movestogo,
+        movetime: params.movetime,
+    };
+
+    limits.time = time::allocate(&clock, move_overhead);
+    limits
+}
+
+fn parse_go(args: &[&str]) -> GoParams {
+    let mut params = GoParams::default();
+    let mut i = 0;
+
+    while i < args.len() {
+        let value = args.get(i + 1).and_then(|v| v.parse::<i64>().ok());
+
+        // Clocks can go negative in some GUIs when the engine is late; treat that as zero
+        let millis = value.map(|v| v.max(0) as u64);
+
+        match args[i] {
+            "depth" => params.depth = value.map(|v| v.max(1) as u32),
+            "movetime" => params.movetime = millis,
+            "wtime" => params.wtime = millis,
+            "btime" => params.btime = millis,
+            "winc" => params.winc = millis,
+            "binc" => params.binc = millis,
+            "movestogo" => params.movestogo = value.map(|v| v.max(1) as u32),
+            "infinite" => {
+                params.infinite = true;
+                i += 1;
+                continue;
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        }
+
+        i += 2;
+    }
+
+    params
+}
+
+/// Match a move in coordinate notation (e2e4, e7e8q, e1g1 or O-O) against the legal moves
+pub fn parse_move(board: &Board, text: &str) -> Option<Move> {
+    generate_legal_moves(board)
+        .iter()
+        .copied()
+        .find(|&m| move_to_uci(m) == text || move_to_string(m) == text)
+}
+
+fn send(line: &str) {
+    let mut stdout = io::stdout().lock();
+    let _ = writeln!(stdout, "{}", line);
+    let _ = stdout.flush();
+}
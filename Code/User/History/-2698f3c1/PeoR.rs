@@ -0,0 +1,121 @@
+use crate::movegen::attacks::attackers_to;
+use crate::movegen::pawns::Bitboard;
+use crate::movegen::queens::BISHOP_MAGICS_TABLE;
+use crate::movegen::rooks::ROOK_MAGICS_TABLE;
+use crate::state::board::{Board, Color, Piece, Square};
+use crate::state::r#move::{
+    from_square, is_castling, is_en_passant, is_promotion, promo_piece, to_square, Move,
+    PROMO_B, PROMO_N, PROMO_R,
+};
+
+/// Piece values used when playing out exchanges (the king can never really be traded)
+pub const SEE_VALUES: [i32; 6] = [100, 320, 330, 500, 900, 20_000];
+
+const PIECES_BY_VALUE: [Piece; 6] = [
+    Piece::Pawn,
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+    Piece::King,
+];
+
+/// Static exchange evaluation: material won (positive) or lost by `m` once both sides
+/// have made every profitable recapture on the destination square
+///
+/// Sliders lined up behind a capturer join in as x-rays when the piece in front leaves.
+pub fn see(board: &Board, m: Move) -> i32 {
+    if is_castling(m) {
+        return 0;
+    }
+
+    let from = from_square(m);
+    let to = to_square(m);
+    let Some((mut piece, mut side)) = board.piece_at(Square(from)) else {
+        return 0;
+    };
+
+    let mut occupancy = board.occupancies[2] ^ (1u64 << from);
+    let mut gain = [0i32; 32];
+
+    // What the first capture wins
+    gain[0] = match board.piece_at(Square(to)) {
+        Some((captured, _)) => SEE_VALUES[captured as usize],
+        None => 0,
+    };
+    if is_en_passant(m) {
+        let captured_sq = if side == Color::White { to - 8 } else { to + 8 };
+        occupancy ^= 1u64 << captured_sq;
+        gain[0] = SEE_VALUES[Piece::Pawn as usize];
+    }
+    if is_promotion(m) {
+        piece = match promo_piece(m) {
+            PROMO_N => Piece::Knight,
+            PROMO_B => Piece::Bishop,
+            PROMO_R => Piece::Rook,
+            _ => Piece::Queen,
+        };
+        gain[0] += SEE_VALUES[piece as usize] - SEE_VALUES[Piece::Pawn as usize];
+    }
+
+    let rook_like = pieces_of_both(board, Piece::Rook) | pieces_of_both(board, Piece::Queen);
+    let bishop_like = pieces_of_both(board, Piece::Bishop) | pieces_of_both(board, Piece::Queen);
+    let mut attackers = attackers_to(board, to, occupancy) & occupancy;
+    let mut depth = 0;
+
+    loop {
+        side = side.opposite();
+
+        // Least valuable piece of the side to recapture
+        let Some((attacker_bb, attacker)) = least_valuable_attacker(board, attackers, side) else {
+            break;
+        };
+
+        depth += 1;
+        if depth >= gain.len() {
+            break;
+        }
+
+        // Speculative gain if the piece on the square is captured now
+        gain[depth] = SEE_VALUES[piece as usize] - gain[depth - 1];
+
+        // Neither side can improve by continuing, so stop early
+        if (-gain[depth - 1]).max(gain[depth]) < 0 {
+            break;
+        }
+
+        occupancy ^= attacker_bb;
+
+        // Removing the capturer may uncover a slider behind it
+        attackers |= ROOK_MAGICS_TABLE[to as usize].attacks(occupancy) & rook_like;
+        attackers |= BISHOP_MAGICS_TABLE[to as usize].get_attacks(occupancy) & bishop_like;
+        attackers &= occupancy;
+
+        piece = attacker;
+    }
+
+    // Negamax the speculative gains back to the first capture
+    while depth > 0 {
+        gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+        depth -= 1;
+    }
+
+    gain[0]
+}
+
+fn pieces_of_both(board: &Board, piece: Piece) -> Bitboard {
+    board.bitboards[Color::White as usize][piece as usize]
+        | board.bitboards[Color::Black as usize][piece as usize]
+}
+
+fn least_valuable_attacker(board: &Board, attackers: Bitboard, side: Color) -> Option<(Bitboard, Piece)> {
+    for piece in PIECES_BY_VALUE {
+        let candidates = attackers & board.bitboards[side as usize][piece as usize];
+        if candidates != 0 {
+            // Isolate a single attacker
+            return Some((candidates & candidates.wrapping_neg(), piece));
+        }
+    }
+
+    None
+}
@@ -0,0 +1,149 @@
+use std::time::Instant;
+
+use crate::movegen::generate::generate_legal_moves;
+use crate::state::board::Board;
+use crate::state::make_move::make_move;
+use crate::state::r#move::move_to_uci;
+use crate::state::state::GameState;
+use crate::state::undo_move::undo_move;
+
+/// Standard perft reference positions (chessprogramming wiki) with known node counts,
+/// indexed by depth - 1
+pub const REFERENCE_POSITIONS: [(&str, &str, &[u64]); 7] = [
+    (
+        "startpos",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        &[20, 400, 8902, 197281, 4865609, 119060324],
+    ),
+    (
+        "kiwipete",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &[48, 2039, 97862, 4085603, 193690690],
+    ),
+    (
+        "position 3",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        &[14, 191, 2812, 43238, 674624, 11030083],
+    ),
+    (
+        "position 4",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        &[6, 264, 9467, 422333, 15833292],
+    ),
+    (
+        "position 4 mirrored",
+        "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        &[6, 264, 9467, 422333, 15833292],
+    ),
+    (
+        "position 5",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        &[44, 1486, 62379, 2103487, 89941194],
+    ),
+    (
+        "position 6",
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        &[46, 2079, 89890, 3894594, 164075551],
+    ),
+];
+
+/// Count leaf nodes of the legal move tree down to `depth`
+pub fn perft(board: &mut Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves = generate_legal_moves(board);
+
+    // Bulk counting: the move list length is the leaf count one ply from the bottom
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+
+    let mut nodes = 0;
+    let mut state = GameState::new();
+
+    for &m in moves.iter() {
+        make_move(board, m, &mut state);
+        nodes += perft(board, depth - 1);
+        undo_move(board, m, &state);
+    }
+
+    nodes
+}
+
+/// Perft split by root move, for diffing against another engine
+pub fn divide(board: &mut Board, depth: u32) -> u64 {
+    let start = Instant::now();
+    let mut total = 0;
+
+    if depth > 0 {
+        let moves = generate_legal_moves(board);
+        let mut state = GameState::new();
+
+        for &m in moves.iter() {
+            make_move(board, m, &mut state);
+            let nodes = perft(board, depth - 1);
+            undo_move(board, m, &state);
+
+            println!("{}: {}", move_to_uci(m), nodes);
+            total += nodes;
+        }
+        println!();
+    } else {
+        total = 1;
+    }
+
+    report(total, start);
+    total
+}
+
+/// Run perft and print the node count with timing
+pub fn run(board: &mut Board, depth: u32) -> u64 {
+    let start = Instant::now();
+    let nodes = perft(board, depth);
+
+    report(nodes, start);
+    nodes
+}
+
+fn report(nodes: u64, start: Instant) {
+    let elapsed = start.elapsed();
+    let nps = (nodes as f64 / elapsed.as_secs_f64().max(1e-9)) as u64;
+
+    println!("Nodes: {}", nodes);
+    println!("Time: {} ms", elapsed.as_millis());
+    println!("NPS: {}", nps);
+}
+
+/// Run every reference position up to `max_depth`, returning true if all counts match
+pub fn suite(max_depth: u32) -> bool {
+    let mut all_passed = true;
+
+    for (name, fen, expected) in REFERENCE_POSITIONS.iter() {
+        let mut board = Board::from_fen(fen).expect("reference FEN is valid");
+
+        for (i, &want) in expected.iter().enumerate().take(max_depth as usize) {
+            let depth = i as u32 + 1;
+            let start = Instant::now();
+            let got = perft(&mut board, depth);
+            let status = if got == want { "ok" } else { "FAIL" };
+
+            println!(
+                "{:<20} depth {}: {:>12} (expected {:>12}) {:>6} ms  {}",
+                name,
+                depth,
+                got,
+                want,
+                start.elapsed().as_millis(),
+                status
+            );
+
+            all_passed &= got == want;
+        }
+    }
+
+    println!();
+    println!("{}", if all_passed { "All positions passed" } else { "Some positions FAILED" });
+    all_passed
+}
@@ -0,0 +1,76 @@
+use crate::eval::material_pst;
+use crate::state::board::{Board, Color, Piece, Square};
+use crate::state::r#move::{from_square, is_castling, is_en_passant, is_promotion, to_square, Move};
+use crate::state::state::GameState;
+use crate::state::zobrist::{compute_hash, compute_pawn_hash, ZOBRIST};
+
+pub fn undo_move(board: &mut Board, mov: Move, state: &GameState) {
+    let from = Square(from_square(mov));
+    let to = Square(to_square(mov));
+    let color = board.side_to_move.opposite();
+
+    // Revert side
+    board.side_to_move = color;
+    board.hash ^= ZOBRIST.side;
+
+    // Undo castling
+    if is_castling(mov) {
+        match to.0 {
+            6 => { // White kingside
+                board.set_piece(Square(7), Some((Piece::Rook, Color::White)));
+                board.set_piece(Square(5), None);
+            }
+            2 => { // White queenside
+                board.set_piece(Square(0), Some((Piece::Rook, Color::White)));
+                board.set_piece(Square(3), None);
+            }
+            62 => { // Black kingside
+                board.set_piece(Square(63), Some((Piece::Rook, Color::Black)));
+                board.set_piece(Square(61), None);
+            }
+            58 => { // Black queenside
+                board.set_piece(Square(56), Some((Piece::Rook, Color::Black)));
+                board.set_piece(Square(59), None);
+            }
+            _ => {}
+        }
+    }
+
+    // The piece that moved (a promoted piece goes back to being a pawn)
+    let piece = if is_promotion(mov) {
+        Piece::Pawn
+    } else {
+        match board.piece_at(to) {
+            Some((piece, _)) => piece,
+            None => return,
+        }
+    };
+
+    // Undo en passant
+    if is_en_passant(mov) {
+        let ep_capture_sq = if color == Color::White {
+            Square(to.0 - 8)
+        } else {
+            Square(to.0 + 8)
+        };
+        board.set_piece(ep_capture_sq, state.captured_piece);
+        board.set_piece(to, None);
+    } else {
+        // Restore captured piece if there was one
+        board.set_piece(to, state.captured_piece);
+    }
+
+    // Restore moved piece
+    board.set_piece(from, Some((piece, color)));
+
+    // Restore castling rights, en passant square, etc.
+    board.restore_state(state);
+
+    debug_assert_eq!(board.hash, compute_hash(board), "incremental hash drifted");
+    debug_assert_eq!(board.pawn_hash, compute_pawn_hash(board), "incremental pawn hash drifted");
+    debug_assert_eq!(
+        board.psqt,
+        [material_pst(board, Color::White), material_pst(board, Color::Black)],
+        "incremental material/PST drifted"
+    );
+}
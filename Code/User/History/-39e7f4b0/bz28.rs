@@ -0,0 +1,49 @@
+// Position keys, read from the `Key:` line of the UCI `d` command.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// Key of the position a `position` command sets up
+fn key(position: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run chess-engine");
+
+    let commands = format!("position {}\nd\nquit\n", position);
+    child.stdin.take().unwrap().write_all(commands.as_bytes()).unwrap();
+
+    let output = child.wait_with_output().expect("chess-engine did not finish");
+    assert!(output.status.success(), "chess-engine exited with {}", output.status);
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("Key: "))
+        .expect("no key in d output")
+        .to_string()
+}
+
+#[test]
+fn en_passant_square_only_counts_when_a_pawn_can_take() {
+    // Nothing can take on e3, so the double step transposes with the same position set up
+    // without an en passant square
+    assert_eq!(
+        key("startpos moves e2e4"),
+        key("fen rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1")
+    );
+    assert_eq!(
+        key("fen rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"),
+        key("fen rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1")
+    );
+
+    // Here exd6 is possible, which makes it a different position
+    assert_ne!(
+        key("startpos moves e2e4 g8f6 e4e5 d7d5"),
+        key("fen rnbqkb1r/ppp1pppp/5n2/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq - 0 3")
+    );
+    assert_eq!(
+        key("startpos moves e2e4 g8f6 e4e5 d7d5"),
+        key("fen rnbqkb1r/ppp1pppp/5n2/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3")
+    );
+}
@@ -0,0 +1,192 @@
+pub type bitboard = u64;
+
+const BOARD_SIZE: usize = 64;
+
+// Precomputed magic numbers for bishops (same set as the queen tables)
+const BISHOP_MAGICS: [bitboard; BOARD_SIZE] = [
+    0x40040844404084, 0x2004208a004208, 0x10190041080202, 0x108060845042010,
+    0x581104180800210, 0x2112080446200010, 0x1080820820060210, 0x3c0808410220200,
+    0x4050404440404, 0x21001420088, 0x24d0080801082102, 0x1020a0a020400,
+    0x40308200402, 0x4011002100800, 0x401484104104005, 0x801010402020200,
+    0x400210c3880100, 0x404022024108200, 0x810018200204102, 0x4002801a02003,
+    0x85040820080400, 0x810102c808880400, 0xe900410884800, 0x8002020480840102,
+    0x220200865090201, 0x2010100a02021202, 0x152048408022401, 0x20080002081110,
+    0x4001001021004000, 0x800040400a011002, 0xe4004081011002, 0x1c004001012080,
+    0x8004200962a00220, 0x8422100208500202, 0x2000402200300c08, 0x8646020080080080,
+    0x80020a0200100808, 0x2010004880111000, 0x623000a080011400, 0x42008c0340209202,
+    0x209188240001000, 0x400408a884001800, 0x110400a6080400, 0x1840060a44020800,
+    0x90080104000041, 0x201011000808101, 0x1a2208080504f080, 0x8012020600211212,
+    0x500861011240000, 0x180806108200800, 0x4000020e01040044, 0x300000261044000a,
+    0x802241102020002, 0x20906061210001, 0x5a84841004010310, 0x4010801011c04,
+    0xa010109502200, 0x4a02012000, 0x500201010098b028, 0x8040002811040900,
+    0x28000010020204, 0x6000020202d0240, 0x8918844842082200, 0x4010011029020020,
+];
+
+// Directions bishop moves in, relative to square index
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [
+    (1, 1),  // NE
+    (1, -1), // NW
+    (-1, 1), // SE
+    (-1, -1) // SW
+];
+
+// Helpers for board coordinates
+fn rank_of(square: usize) -> usize { square / 8 }
+fn file_of(square: usize) -> usize { square % 8 }
+
+// Generate mask of relevant bishop blockers for a square (exclude edges)
+fn bishop_mask(square: usize) -> bitboard {
+    let mut mask = 0;
+
+    let r = rank_of(square) as i32;
+    let f = file_of(square) as i32;
+
+    for &(dr, df) in &BISHOP_DIRECTIONS {
+        let mut rr = r + dr;
+        let mut ff = f + df;
+
+        // Stop one short of the edges (exclude edge squares)
+        while rr > 0 && rr < 7 && ff > 0 && ff < 7 {
+            mask |= 1u64 << (rr * 8 + ff);
+            rr += dr;
+            ff += df;
+        }
+    }
+
+    mask
+}
+
+// Generate bishop attacks for a given blockers set (used to precompute)
+fn bishop_attack_on_the_fly(square: usize, blockers: bitboard) -> bitboard {
+    let mut attacks = 0;
+
+    let r = rank_of(square) as i32;
+    let f = file_of(square) as i32;
+
+    for &(dr, df) in &BISHOP_DIRECTIONS {
+        let mut rr = r + dr;
+        let mut ff = f + df;
+
+        while rr >= 0 && rr < 8 && ff >= 0 && ff < 8 {
+            let sq = (rr * 8 + ff) as usize;
+            attacks |= 1u64 << sq;
+
+            if blockers & (1u64 << sq) != 0 {
+                break; // Blocked by piece
+            }
+
+            rr += dr;
+            ff += df;
+        }
+    }
+
+    attacks
+}
+
+// Given an index for bits in mask, generate a blocker bitboard for the occupancy variation
+fn set_occupancy(index: usize, bits_in_mask: usize, mask: bitboard) -> bitboard {
+    let mut blockers = 0;
+    let mut bit_index = 0;
+
+    for sq in 0..64 {
+        let bit = 1u64 << sq;
+
+        if mask & bit != 0 {
+            if (index & (1 << bit_index)) != 0 {
+                blockers |= bit;
+            }
+            bit_index += 1;
+
+            if bit_index == bits_in_mask {
+                break;
+            }
+        }
+    }
+
+    blockers
+}
+
+// Struct storing bishop attack data and tables
+pub struct BishopMagic {
+    masks: [bitboard; BOARD_SIZE],
+    magics: [bitboard; BOARD_SIZE],
+    attack_table_offsets: [usize; BOARD_SIZE], // Starting index in big attack table
+    attack_table: Vec<bitboard>,               // Flat attack table for all squares
+}
+
+impl BishopMagic {
+    // Initialize and precompute all tables
+    pub fn new() -> Self {
+        let mut masks = [0; BOARD_SIZE];
+        let mut attack_table_offsets = [0; BOARD_SIZE];
+        let magics = BISHOP_MAGICS;
+
+        // Count total table size (sum of 2^(bits in mask) for all squares)
+        let mut total_size = 0;
+        for sq in 0..BOARD_SIZE {
+            masks[sq] = bishop_mask(sq);
+            total_size += 1 << masks[sq].count_ones();
+        }
+
+        let mut attack_table = Vec::with_capacity(total_size);
+
+        let mut offset = 0;
+        for sq in 0..BOARD_SIZE {
+            attack_table_offsets[sq] = offset;
+
+            let mask = masks[sq];
+            let bits = mask.count_ones() as usize;
+            let table_size = 1 << bits;
+
+            // Store each attack set at the slot its magic index points to
+            attack_table.resize(offset + table_size, 0);
+            for index in 0..table_size {
+                let blockers = set_occupancy(index, bits, mask);
+                let magic_index = (blockers.wrapping_mul(magics[sq]) >> (64 - bits)) as usize;
+                attack_table[offset + magic_index] = bishop_attack_on_the_fly(sq, blockers);
+            }
+
+            offset += table_size;
+        }
+
+        Self {
+            masks,
+            magics,
+            attack_table_offsets,
+            attack_table,
+        }
+    }
+
+    // Compute bishop attacks from precomputed tables using magic indexing
+    pub fn bishop_attacks(&self, square: usize, occupied: bitboard) -> bitboard {
+        let mask = self.masks[square];
+        let magic = self.magics[square];
+        let relevant_occupancy = occupied & mask;
+        let bits_in_mask = mask.count_ones();
+
+        // Magic indexing: multiply & shift
+        let index = ((relevant_occupancy.wrapping_mul(magic)) >> (64 - bits_in_mask)) as usize;
+
+        let offset = self.attack_table_offsets[square];
+        self.attack_table[offset + index]
+    }
+
+    // Compute bishop moves for all bishops on board
+    pub fn bishop_moves(&self, bishops: bitboard, occupied: bitboard) -> bitboard {
+        let mut moves = 0;
+        let mut bb = bishops;
+
+        while bb != 0 {
+            let sq = bb.trailing_zeros() as usize;
+            moves |= self.bishop_attacks(sq, occupied);
+            bb &= bb - 1;
+        }
+
+        moves
+    }
+}
+
+// Shared bishop tables, built once on first use
+lazy_static::lazy_static! {
+    pub static ref BISHOP_MAGIC: BishopMagic = BishopMagic::new();
+}
@@ -0,0 +1,328 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+use crate::search::{format_score, search, SearchLimits, SearchOptions, SearchResult};
+use crate::state::board::{Board, FenError};
+use crate::state::r#move::Move;
+use crate::state::san::{move_to_san, parse_san, SanError};
+use crate::time::TimeLimits;
+use crate::tt::{TranspositionTable, DEFAULT_HASH_MB};
+
+/// A test position from an EPD file, with the operations the runner understands
+///
+/// `hmvc` and `fmvn` set the move clocks; other opcodes are accepted and ignored.
+pub struct EpdRecord {
+    pub board: Board,
+    pub id: Option<String>,
+    pub best_moves: Vec<Move>,  // bm
+    pub avoid_moves: Vec<Move>, // am
+    pub comment: Option<String>, // c0
+}
+
+/// Reasons an EPD line can't be used
+#[derive(Clone, PartialEq, Debug)]
+pub enum EpdError {
+    MissingFields,
+    Fen(FenError),
+    San(&'static str, SanError), // opcode and the move it didn't accept
+    UnterminatedString,
+    NoSolution, // neither bm nor am, so nothing to score against
+}
+
+impl fmt::Display for EpdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EpdError::MissingFields => write!(f, "expected 4 position fields"),
+            EpdError::Fen(e) => write!(f, "invalid position: {}", e),
+            EpdError::San(opcode, e) => write!(f, "{}: {}", opcode, e),
+            EpdError::UnterminatedString => write!(f, "unterminated string operand"),
+            EpdError::NoSolution => write!(f, "no bm or am operation"),
+        }
+    }
+}
+
+impl std::error::Error for EpdError {}
+
+/// Parse one EPD line: four FEN fields, then `opcode operand...;` operations
+pub fn parse_record(line: &str) -> Result<EpdRecord, EpdError> {
+    let mut rest = line.trim();
+    let mut fields = Vec::new();
+    for _ in 0..4 {
+        let (field, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        if field.is_empty() {
+            return Err(EpdError::MissingFields);
+        }
+        fields.push(field);
+        rest = tail.trim_start();
+    }
+
+    let operations = parse_operations(rest)?;
+    let operand = |opcode: &str| {
+        operations
+            .iter()
+            .find(|(op, _)| op == opcode)
+            .and_then(|(_, operands)| operands.first().cloned())
+    };
+
+    let fen = format!(
+        "{} {} {}",
+        fields.join(" "),
+        operand("hmvc").unwrap_or_else(|| "0".to_string()),
+        operand("fmvn").unwrap_or_else(|| "1".to_string())
+    );
+    let board = Board::from_fen(&fen).map_err(EpdError::Fen)?;
+
+    let moves = |opcode: &'static str| -> Result<Vec<Move>, EpdError> {
+        operations
+            .iter()
+            .filter(|(op, _)| op == opcode)
+            .flat_map(|(_, operands)| operands)
+            .map(|san| parse_san(&board, san).map_err(|e| EpdError::San(opcode, e)))
+            .collect()
+    };
+    let best_moves = moves("bm")?;
+    let avoid_moves = moves("am")?;
+
+    if best_moves.is_empty() && avoid_moves.is_empty() {
+        return Err(EpdError::NoSolution);
+    }
+
+    Ok(EpdRecord {
+        id: operand("id"),
+        comment: operand("c0"),
+        board,
+        best_moves,
+        avoid_moves,
+    })
+}
+
+// Split `op a b; op "quoted; string";` into opcodes and operands
+fn parse_operations(text: &str) -> Result<Vec<(String, Vec<String>)>, EpdError> {
+    let mut operations = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => {
+                if !words.is_empty() {
+                    let opcode = words.remove(0);
+                    operations.push((opcode, std::mem::take(&mut words)));
+                }
+            }
+            '"' => {
+                let mut word = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(EpdError::UnterminatedString),
+                    }
+                }
+                words.push(word);
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != ';') {
+                    word.push(c);
+                }
+                words.push(word);
+            }
+        }
+    }
+
+    // The last operation may be missing its semicolon
+    if !words.is_empty() {
+        let opcode = words.remove(0);
+        operations.push((opcode, words));
+    }
+
+    Ok(operations)
+}
+
+/// How the engine did on one record
+pub struct Outcome {
+    pub played: Option<Move>,
+    pub solved: bool,
+    pub time_to_solution: Option<Duration>, // since when the answer has been right for good
+    pub result: SearchResult,
+}
+
+impl EpdRecord {
+    fn accepts(&self, m: Move) -> bool {
+        (self.best_moves.is_empty() || self.best_moves.contains(&m)) && !self.avoid_moves.contains(&m)
+    }
+
+    /// Search the position for `movetime` and judge the move the engine settles on
+    pub fn solve(&self, movetime: Duration, threads: usize, tt: &TranspositionTable) -> Outcome {
+        let limits = SearchLimits {
+            depth: None,
+            time: Some(TimeLimits {
+                soft: movetime,
+                hard: movetime,
+            }),
+        };
+        let options = SearchOptions {
+            info: false,
+            ..SearchOptions::default()
+        };
+
+        let start = Instant::now();
+        let result = search(&self.board, limits, options, threads, &AtomicBool::new(false), tt);
+        let elapsed = start.elapsed();
+
+        let solved = result.best_move.is_some_and(|m| self.accepts(m));
+
+        // The first iteration after which the answer never changed back
+        let time_to_solution = solved.then(|| {
+            let settled = result.history.iter().rposition(|&(_, m)| !self.accepts(m)).map_or(0, |i| i + 1);
+            result.history.get(settled).map_or(elapsed, |&(time, _)| time)
+        });
+
+        Outcome {
+            played: result.best_move,
+            solved,
+            time_to_solution,
+            result,
+        }
+    }
+}
+
+/// Run every position of an EPD file, printing a line per position and a summary, and
+/// optionally writing the results as JSON
+pub fn run(path: &str, movetime: u64, threads: usize, json: Option<&str>) -> io::Result<()> {
+    let text = fs::read_to_string(path)?;
+    let tt = TranspositionTable::new(DEFAULT_HASH_MB);
+    let movetime = Duration::from_millis(movetime);
+
+    let mut results = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        let record = match parse_record(line) {
+            Ok(record) => record,
+            Err(e) => {
+                eprintln!("{}:{}: {}", path, number + 1, e);
+                continue;
+            }
+        };
+
+        // Each position starts from a clean slate
+        tt.clear();
+        let outcome = record.solve(movetime, threads, &tt);
+
+        let id = record.id.clone().unwrap_or_else(|| format!("line {}", number + 1));
+        println!(
+            "{:<12} {:<6} {:<8} {:<20} {:>8}  depth {:<3} {}",
+            id,
+            if outcome.solved { "solved" } else { "failed" },
+            outcome.played.map_or("-".to_string(), |m| move_to_san(&record.board, m)),
+            expected(&record),
+            outcome.time_to_solution.map_or("-".to_string(), |t| format!("{:.2}s", t.as_secs_f64())),
+            outcome.result.depth,
+            format_score(outcome.result.score)
+        );
+
+        results.push((record, outcome));
+    }
+
+    let solved: Vec<Duration> = results.iter().filter_map(|(_, outcome)| outcome.time_to_solution).collect();
+    let total = results.len();
+    let score = if total > 0 { 100.0 * solved.len() as f64 / total as f64 } else { 0.0 };
+
+    println!();
+    println!("Solved: {} / {}", solved.len(), total);
+    println!("Failed: {}", total - solved.len());
+    if !solved.is_empty() {
+        let sum: Duration = solved.iter().sum();
+        let average = sum.as_secs_f64() / solved.len() as f64;
+        println!("Time to solution: {:.2}s total, {:.2}s average", sum.as_secs_f64(), average);
+    }
+    println!("Score: {:.1}%", score);
+
+    if let Some(json_path) = json {
+        fs::write(json_path, to_json(path, movetime, threads, &results))?;
+    }
+
+    Ok(())
+}
+
+// What the record asks for, as written in EPD
+fn expected(record: &EpdRecord) -> String {
+    let san = |moves: &[Move]| moves.iter().map(|&m| move_to_san(&record.board, m)).collect::<Vec<_>>().join(" ");
+
+    let mut parts = Vec::new();
+    if !record.best_moves.is_empty() {
+        parts.push(format!("bm {}", san(&record.best_moves)));
+    }
+    if !record.avoid_moves.is_empty() {
+        parts.push(format!("am {}", san(&record.avoid_moves)));
+    }
+    parts.join(", ")
+}
+
+fn to_json(path: &str, movetime: Duration, threads: usize, results: &[(EpdRecord, Outcome)]) -> String {
+    let solved = results.iter().filter(|(_, outcome)| outcome.solved).count();
+    let score = if results.is_empty() { 0.0 } else { 100.0 * solved as f64 / results.len() as f64 };
+
+    let positions: Vec<String> = results
+        .iter()
+        .map(|(record, outcome)| {
+            let san_list = |moves: &[Move]| {
+                let quoted: Vec<String> = moves.iter().map(|&m| json_string(&move_to_san(&record.board, m))).collect();
+                format!("[{}]", quoted.join(", "))
+            };
+            let optional = |value: &Option<String>| value.as_deref().map_or("null".to_string(), json_string);
+
+            format!(
+                "    {{\"id\": {}, \"fen\": {}, \"bm\": {}, \"am\": {}, \"c0\": {}, \"move\": {}, \"solved\": {}, \
+                 \"time_to_solution_ms\": {}, \"depth\": {}, \"nodes\": {}, \"score\": {}}}",
+                optional(&record.id),
+                json_string(&record.board.to_fen()),
+                san_list(&record.best_moves),
+                san_list(&record.avoid_moves),
+                optional(&record.comment),
+                optional(&outcome.played.map(|m| move_to_san(&record.board, m))),
+                outcome.solved,
+                outcome.time_to_solution.map_or("null".to_string(), |t| t.as_millis().to_string()),
+                outcome.result.depth,
+                outcome.result.nodes,
+                json_string(&format_score(outcome.result.score))
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\n  \"file\": {},\n  \"movetime_ms\": {},\n  \"threads\": {},\n  \"total\": {},\n  \"solved\": {},\n  \
+         \"failed\": {},\n  \"score\": {:.1},\n  \"positions\": [\n{}\n  ]\n}}\n",
+        json_string(path),
+        movetime.as_millis(),
+        threads,
+        results.len(),
+        solved,
+        results.len() - solved,
+        score,
+        positions.join(",\n")
+    )
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
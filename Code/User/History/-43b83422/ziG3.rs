@@ -0,0 +1,88 @@
+// PGN reading and writing, run through the `pgn` subcommand of the binary.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/games.pgn");
+
+fn pgn(path: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .args(["pgn", path])
+        .output()
+        .expect("failed to run chess-engine")
+}
+
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("chess-engine-{}-{}.pgn", name, std::process::id()));
+    fs::write(&path, contents).expect("failed to write temporary PGN");
+    path
+}
+
+#[test]
+fn reads_and_writes_every_game() {
+    let output = pgn(FIXTURE);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(text.matches("[Event ").count(), 3);
+
+    // Tags, escapes, comments, NAGs (suffixes become $n) and nested variations survive
+    assert!(text.contains("[Annotator \"Test \\\"quoted\\\" name\"]"));
+    assert!(text.contains("{The Opera Game.} 1. e4 e5"));
+    assert!(text.contains("Bg4 $2 {This is a weak move"));
+    assert!(text.contains("10. Nxb5 $1 cxb5"));
+    assert!(text.contains("(14... Qb4 15. Bxf6 (15. Qxb4 $2) 15... gxf6)"));
+    assert!(text.contains("17. Rd8# 1-0"));
+
+    // Sloppy SAN comes out clean
+    assert!(text.contains("2. Nf3 Nc6"));
+    assert!(text.contains("4. O-O Nxe4"));
+    assert!(text.contains("11. Nxe6 fxe6"));
+    assert!(text.contains("Bd6 $5"));
+
+    // Games set up from a FEN start with Black's move number
+    assert!(text.contains("60... Kb3 61. b8=Q+ (61. b8=N Kc4) 61... Kc4"));
+
+    assert!(text.lines().all(|line| line.len() <= 79));
+}
+
+#[test]
+fn output_reads_back_unchanged() {
+    let first = pgn(FIXTURE);
+    let path = temp_file("round-trip", &String::from_utf8_lossy(&first.stdout));
+    let second = pgn(path.to_str().unwrap());
+    fs::remove_file(&path).ok();
+
+    assert!(second.status.success());
+    assert_eq!(first.stdout, second.stdout);
+}
+
+#[test]
+fn closing_brace_in_a_comment_is_replaced() {
+    let text = "[Event \"Braces\"]\n\n1. e4 ; best by test} says who\ne5 *\n";
+    let path = temp_file("brace", text);
+    let first = pgn(path.to_str().unwrap());
+    fs::write(&path, &first.stdout).unwrap();
+    let second = pgn(path.to_str().unwrap());
+    fs::remove_file(&path).ok();
+
+    assert!(first.status.success() && second.status.success());
+    assert!(String::from_utf8_lossy(&first.stdout).contains("1. e4 {best by test) says who} 1... e5 *"));
+    assert_eq!(first.stdout, second.stdout);
+}
+
+#[test]
+fn illegal_moves_are_reported() {
+    let text = "[Event \"Good\"]\n\n1. e4 e5 *\n\n[Event \"Bad\"]\n\n1. e4 e5 2. Ke3 *\n";
+    let path = temp_file("illegal", text);
+    let output = pgn(path.to_str().unwrap());
+    fs::remove_file(&path).ok();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("[Event \"Good\"]"));
+
+    let errors = String::from_utf8_lossy(&output.stderr);
+    assert!(errors.contains("game 2: at 2. illegal move 'Ke3'"), "{}", errors);
+}
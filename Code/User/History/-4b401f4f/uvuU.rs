@@ -0,0 +1,301 @@
+use std::io::{BufRead, Lines};
+
+use crate::pgn::{move_number, play, Game, PgnError, PgnMove, Variation};
+use crate::state::board::Board;
+use crate::state::san::parse_san;
+
+// Old-style move suffixes and the NAGs they stand for
+const SUFFIX_NAGS: [(&str, u8); 6] = [("!", 1), ("?", 2), ("!!", 3), ("??", 4), ("!?", 5), ("?!", 6)];
+
+/// Reads the games of a PGN stream one at a time
+///
+/// Every move is resolved against the position it is played in and made with `make_move`,
+/// so a game that comes out of the reader is known to be legal, side lines included.
+pub struct PgnReader<R: BufRead> {
+    lines: Lines<R>,
+    pending: Option<String>, // first tag line of the next game, read while finishing this one
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            pending: None,
+        }
+    }
+
+    // The tag lines and the movetext of the next game, or None at the end of the input
+    fn next_chunk(&mut self) -> Option<Result<(Vec<String>, String), PgnError>> {
+        let mut tags = Vec::new();
+        let mut movetext = String::new();
+        let mut open_comments = 0i32;
+
+        loop {
+            let line = match self.pending.take() {
+                Some(line) => line,
+                None => match self.lines.next() {
+                    Some(Ok(line)) => line,
+                    Some(Err(e)) => return Some(Err(PgnError::Io(e))),
+                    None => break,
+                },
+            };
+            let trimmed = line.trim();
+
+            // Escaped lines are for other programs
+            if trimmed.starts_with('%') {
+                continue;
+            }
+
+            if trimmed.starts_with('[') && open_comments == 0 {
+                // A tag after the movetext belongs to the next game
+                if !movetext.trim().is_empty() {
+                    self.pending = Some(line);
+                    break;
+                }
+                tags.push(trimmed.to_string());
+            } else {
+                open_comments += trimmed.matches('{').count() as i32 - trimmed.matches('}').count() as i32;
+                movetext.push_str(trimmed);
+                movetext.push('\n');
+            }
+        }
+
+        if tags.is_empty() && movetext.trim().is_empty() {
+            None
+        } else {
+            Some(Ok((tags, movetext)))
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<Game, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_chunk()?.and_then(|(tags, movetext)| parse_game(&tags, &movetext)))
+    }
+}
+
+/// Parse a single game from PGN text
+pub fn parse_game(tag_lines: &[String], movetext: &str) -> Result<Game, PgnError> {
+    let mut game = Game::default();
+    for line in tag_lines {
+        for (name, value) in parse_tags(line)? {
+            game.set_tag(&name, &value);
+        }
+    }
+
+    let tokens = tokenize(movetext)?;
+    let mut pos = 0;
+    let mut result = None;
+    game.mainline = parse_variation(&tokens, &mut pos, game.start_board()?, 0, &mut result)?;
+
+    // The termination marker stands in for a missing Result tag
+    if game.tag("Result").is_none() {
+        game.set_tag("Result", result.as_deref().unwrap_or("*"));
+    }
+
+    Ok(game)
+}
+
+// Every `[Name "value"]` pair on a line
+fn parse_tags(line: &str) -> Result<Vec<(String, String)>, PgnError> {
+    let malformed = || PgnError::Tag(line.to_string());
+    let mut tags = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            Some('[') => {}
+            None => return Ok(tags),
+            Some(_) => return Err(malformed()),
+        }
+
+        let mut name = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+            name.push(c);
+        }
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if name.is_empty() || chars.next() != Some('"') {
+            return Err(malformed());
+        }
+
+        let mut value = String::new();
+        loop {
+            match chars.next() {
+                Some('\\') => value.push(chars.next().ok_or_else(malformed)?),
+                Some('"') => break,
+                Some(c) => value.push(c),
+                None => return Err(malformed()),
+            }
+        }
+
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.next() != Some(']') {
+            return Err(malformed());
+        }
+        tags.push((name, value));
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Move(String),
+    Nag(u8),
+    Comment(String),
+    Open,
+    Close,
+    Result(String),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, PgnError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '{' => {
+                let mut comment = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => comment.push(c),
+                        None => return Err(PgnError::Syntax("unterminated comment".to_string())),
+                    }
+                }
+                tokens.push(Token::Comment(comment.split_whitespace().collect::<Vec<_>>().join(" ")));
+            }
+            ';' => {
+                let comment: String = chars.by_ref().take_while(|&c| c != '\n').collect();
+                tokens.push(Token::Comment(comment.trim().to_string()));
+            }
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '*' => tokens.push(Token::Result("*".to_string())),
+            '$' => {
+                let mut digits = String::new();
+                while let Some(d) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(d);
+                }
+                let nag = digits.parse().map_err(|_| PgnError::Syntax(format!("bad NAG '${}'", digits)))?;
+                tokens.push(Token::Nag(nag));
+            }
+            _ => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && !"{};()$".contains(c)) {
+                    word.push(c);
+                }
+                symbol_tokens(&word, &mut tokens);
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+// A move, move number, result or combination like "12.Nf3!?"
+fn symbol_tokens(word: &str, tokens: &mut Vec<Token>) {
+    if matches!(word, "1-0" | "0-1" | "1/2-1/2") {
+        tokens.push(Token::Result(word.to_string()));
+        return;
+    }
+
+    // Move numbers, possibly run together with the move ("1.e4", "3...Nc6"); castling
+    // with zeros ("0-0") is the one move that starts with a digit
+    let word = if word.starts_with("0-0") {
+        word
+    } else {
+        word.trim_start_matches(|c: char| c.is_ascii_digit()).trim_start_matches('.')
+    };
+
+    // What some files write after an en passant capture
+    if word.is_empty() || word == "e.p." {
+        return;
+    }
+
+    let san = word.trim_end_matches(['!', '?']);
+    tokens.push(Token::Move(san.to_string()));
+
+    let suffix = &word[san.len()..];
+    if let Some(&(_, nag)) = SUFFIX_NAGS.iter().find(|(text, _)| *text == suffix) {
+        tokens.push(Token::Nag(nag));
+    }
+}
+
+// Moves from `board` up to the closing parenthesis of a side line (depth > 0) or the end
+// of the game
+fn parse_variation(
+    tokens: &[Token],
+    pos: &mut usize,
+    mut board: Board,
+    depth: usize,
+    result: &mut Option<String>,
+) -> Result<Variation, PgnError> {
+    let mut variation = Variation::default();
+
+    // Side lines branch off from the position before the latest move
+    let mut before_last = board.clone();
+
+    while let Some(token) = tokens.get(*pos) {
+        *pos += 1;
+
+        match token {
+            Token::Move(text) => {
+                let mov = parse_san(&board, text).map_err(|error| PgnError::San {
+                    at: move_number(&board),
+                    error,
+                })?;
+                before_last = board.clone();
+                play(&mut board, mov)?;
+                variation.moves.push(PgnMove::new(mov));
+            }
+            Token::Nag(nag) => match variation.moves.last_mut() {
+                Some(last) => last.nags.push(*nag),
+                None => return Err(PgnError::Syntax(format!("NAG ${} before any move", nag))),
+            },
+            Token::Comment(comment) => {
+                let target = match variation.moves.last_mut() {
+                    Some(last) => &mut last.comment,
+                    None => &mut variation.comment,
+                };
+
+                // Comments in a row are kept together
+                match target {
+                    Some(existing) => {
+                        existing.push(' ');
+                        existing.push_str(comment);
+                    }
+                    None => *target = Some(comment.clone()),
+                }
+            }
+            Token::Open => {
+                if variation.moves.is_empty() {
+                    return Err(PgnError::Syntax("variation before any move".to_string()));
+                }
+
+                let side_line = parse_variation(tokens, pos, before_last.clone(), depth + 1, result)?;
+                variation.moves.last_mut().unwrap().variations.push(side_line);
+            }
+            Token::Close => {
+                if depth == 0 {
+                    return Err(PgnError::Syntax("unmatched ')'".to_string()));
+                }
+                return Ok(variation);
+            }
+            Token::Result(marker) => {
+                if depth == 0 {
+                    *result = Some(marker.clone());
+                    break;
+                }
+            }
+        }
+    }
+
+    if depth > 0 {
+        return Err(PgnError::Syntax("unterminated variation".to_string()));
+    }
+
+    Ok(variation)
+}
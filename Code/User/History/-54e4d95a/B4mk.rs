@@ -0,0 +1,46 @@
+// FEN validation, seen through the error `divide` reports for a position it won't load.
+
+use std::process::Command;
+
+// The error message for a FEN, or None if it loads
+fn fen_error(fen: &str) -> Option<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .args(["divide", "1", fen])
+        .output()
+        .expect("failed to run chess-engine");
+
+    if output.status.success() {
+        None
+    } else {
+        Some(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[test]
+fn castling_rights_need_king_and_rook_at_home() {
+    assert_eq!(fen_error("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1"), None);
+
+    let error = Some("invalid fen: invalid castling rights 'K'".to_string());
+    assert_eq!(fen_error("4k3/8/8/8/8/8/8/4K3 w K - 0 1"), error);
+    assert_eq!(fen_error("4k3/8/8/8/8/8/8/3K3R w K - 0 1"), error);
+    assert!(fen_error("r3k2r/8/8/8/8/8/8/1R2K2R w KQkq - 0 1").is_some());
+    assert!(fen_error("r3k1r1/8/8/8/8/8/8/R3K2R w KQkq - 0 1").is_some());
+}
+
+#[test]
+fn one_king_per_side() {
+    let error = |message: &str| Some(format!("invalid fen: {}", message));
+
+    assert_eq!(fen_error("8/8/8/8/8/8/8/8 w - - 0 1"), error("expected one white king, found 0"));
+    assert_eq!(fen_error("4k3/8/8/8/8/8/8/K6K w - - 0 1"), error("expected one white king, found 2"));
+    assert_eq!(fen_error("8/8/8/8/8/8/8/4K3 b - - 0 1"), error("expected one black king, found 0"));
+}
+
+#[test]
+fn en_passant_square_matches_side_to_move() {
+    assert_eq!(fen_error("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1"), None);
+    assert_eq!(fen_error("4k3/8/8/8/3Pp3/8/8/4K3 b - d3 0 1"), None);
+
+    assert!(fen_error("4k3/8/8/3pP3/8/8/8/4K3 w - d3 0 1").is_some());
+    assert!(fen_error("4k3/8/8/8/3Pp3/8/8/4K3 b - d6 0 1").is_some());
+}
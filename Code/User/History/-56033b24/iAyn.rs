@@ -0,0 +1,62 @@
+// The EPD test-suite runner, on the WAC sample in tests/data.
+
+use std::env;
+use std::fs;
+use std::process::{Command, Output};
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/wac.epd");
+
+fn epd(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .arg("epd")
+        .args(args)
+        .output()
+        .expect("failed to run chess-engine")
+}
+
+#[test]
+fn runs_the_suite_and_writes_json() {
+    let json_path = env::temp_dir().join(format!("chess-engine-wac-{}.json", std::process::id()));
+    let output = epd(&[FIXTURE, "--movetime", "100", "--json", json_path.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let report = String::from_utf8_lossy(&output.stdout);
+    let line = |id: &str| report.lines().find(|line| line.starts_with(id)).unwrap_or_default().to_string();
+
+    // Every record gets a line; the mates in two and the am record are found even in a debug build
+    assert_eq!(report.lines().filter(|line| line.contains(" solved ") || line.contains(" failed ")).count(), 6);
+    assert!(line("WAC.004").contains("solved Qxh7+"), "{}", report);
+    assert!(line("AM.001").contains("solved") && line("AM.001").contains("am Ng5"), "{}", report);
+    assert!(report.contains("Solved: "));
+    assert!(report.contains("Score: "));
+
+    let json = fs::read_to_string(&json_path).expect("no JSON written");
+    fs::remove_file(&json_path).ok();
+
+    assert!(json.contains("\"movetime_ms\": 100"));
+    assert!(json.contains("\"total\": 6"));
+    assert!(json.contains("\"id\": \"WAC.001\""));
+    assert!(json.contains("\"bm\": [\"Qg6\"]"));
+    assert!(json.contains("\"am\": [\"Ng5\"], \"c0\": \"Ng5 drops the knight to Qxg5\""));
+}
+
+#[test]
+fn bad_records_are_skipped_with_a_warning() {
+    let path = env::temp_dir().join(format!("chess-engine-bad-{}.epd", std::process::id()));
+    let text = "8/8/8/4k3/8/8/3K4/8 w - - bm Qh5; id \"no queen\";\n\
+                7k/6Q1/6K1/8/8/8/8/8 w - -\n\
+                6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id \"back rank\";\n";
+    fs::write(&path, text).unwrap();
+    let output = epd(&[path.to_str().unwrap(), "--movetime", "50"]);
+    fs::remove_file(&path).ok();
+
+    assert!(output.status.success());
+
+    let errors = String::from_utf8_lossy(&output.stderr);
+    assert!(errors.contains(":1: bm: illegal move 'Qh5'"), "{}", errors);
+    assert!(errors.contains(":2: no bm or am operation"), "{}", errors);
+
+    let report = String::from_utf8_lossy(&output.stdout);
+    assert!(report.contains("back rank"));
+    assert!(report.contains("Solved: 1 / 1"));
+}
@@ -0,0 +1,331 @@
+use std::fmt;
+
+use crate::movegen::generate::generate_legal_moves;
+use crate::state::board::{coord_to_square, Board, Piece, Square};
+use crate::state::make_move::make_move;
+use crate::state::r#move::{
+    from_square, is_castling, is_en_passant, is_promotion, promo_piece, square_to_coord, to_square, Move, PROMO_B,
+    PROMO_N, PROMO_NONE, PROMO_Q, PROMO_R,
+};
+use crate::state::state::GameState;
+
+/// Reasons a SAN move can't be matched to a legal move by `parse_san`
+#[derive(Clone, PartialEq, Debug)]
+pub enum SanError {
+    Malformed(String),
+    Illegal(String),
+    Ambiguous(String),
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SanError::Malformed(s) => write!(f, "malformed move '{}'", s),
+            SanError::Illegal(s) => write!(f, "illegal move '{}'", s),
+            SanError::Ambiguous(s) => write!(f, "ambiguous move '{}'", s),
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
+
+fn piece_letter(piece: Piece) -> &'static str {
+    match piece {
+        Piece::Pawn => "",
+        Piece::Knight => "N",
+        Piece::Bishop => "B",
+        Piece::Rook => "R",
+        Piece::Queen => "Q",
+        Piece::King => "K",
+    }
+}
+
+fn promo_letter(promo: u32) -> &'static str {
+    match promo {
+        PROMO_N => "N",
+        PROMO_B => "B",
+        PROMO_R => "R",
+        _ => "Q",
+    }
+}
+
+/// Standard algebraic notation for a legal move in `board` (e.g. Nbd7, exd6, e8=Q+, O-O#)
+pub fn move_to_san(board: &Board, m: Move) -> String {
+    let from = from_square(m);
+    let to = to_square(m);
+
+    let mut san = if is_castling(m) {
+        if to % 8 == 6 { "O-O".to_string() } else { "O-O-O".to_string() }
+    } else {
+        let (piece, _) = board.piece_at(Square(from)).expect("SAN of a move from an empty square");
+        let capture = is_en_passant(m) || board.piece_at(Square(to)).is_some();
+        let mut san = piece_letter(piece).to_string();
+
+        if piece == Piece::Pawn {
+            if capture {
+                san.push((b'a' + from % 8) as char);
+            }
+        } else {
+            san.push_str(&disambiguation(board, m, piece));
+        }
+
+        if capture {
+            san.push('x');
+        }
+        san.push_str(&square_to_coord(to));
+
+        if is_promotion(m) {
+            san.push('=');
+            san.push_str(promo_letter(promo_piece(m)));
+        }
+
+        san
+    };
+
+    // Check or mate, seen from the position after the move
+    let mut after = board.clone();
+    make_move(&mut after, m, &mut GameState::new());
+    if after.in_check() {
+        san.push(if generate_legal_moves(&after).is_empty() { '#' } else { '+' });
+    }
+
+    san
+}
+
+/// `Move` is a plain integer, so `m.to_san(&board)` needs this trait in scope
+pub trait ToSan {
+    /// Standard algebraic notation for this move, legal in `board`
+    fn to_san(self, board: &Board) -> String;
+}
+
+impl ToSan for Move {
+    fn to_san(self, board: &Board) -> String {
+        move_to_san(board, self)
+    }
+}
+
+// As little of the from square as tells the move apart from others by the same kind of
+// piece to the same square: the file if that does it, else the rank, else both
+fn disambiguation(board: &Board, m: Move, piece: Piece) -> String {
+    let from = from_square(m);
+    let rivals: Vec<u8> = generate_legal_moves(board)
+        .iter()
+        .filter(|&&other| other != m && to_square(other) == to_square(m) && from_square(other) != from)
+        .filter(|&&other| board.piece_at(Square(from_square(other))).map(|(p, _)| p) == Some(piece))
+        .map(|&other| from_square(other))
+        .collect();
+
+    let coord = square_to_coord(from);
+    if rivals.is_empty() {
+        String::new()
+    } else if rivals.iter().all(|&sq| sq % 8 != from % 8) {
+        coord[..1].to_string()
+    } else if rivals.iter().all(|&sq| sq / 8 != from / 8) {
+        coord[1..].to_string()
+    } else {
+        coord
+    }
+}
+
+/// Match a move in SAN against the legal moves of `board`
+///
+/// Forgives the usual sloppiness: a missing `x` or `=`, `0-0` for `O-O`, lower-case
+/// promotion pieces (`e8q`), over-specified from squares (`Ng1f3`) and trailing check
+/// marks or annotations (`+`, `#`, `!?`).
+pub fn parse_san(board: &Board, text: &str) -> Result<Move, SanError> {
+    let malformed = || SanError::Malformed(text.to_string());
+
+    let trimmed = text.trim().trim_end_matches(['+', '#', '!', '?']);
+    let trimmed = trimmed.strip_suffix("e.p.").unwrap_or(trimmed).trim_end();
+
+    let moves = generate_legal_moves(board);
+
+    // Castling, with letter O or digit zero
+    let castle = trimmed.replace('0', "O");
+    if castle == "O-O" || castle == "O-O-O" {
+        let file = if castle == "O-O" { 6 } else { 2 };
+        return moves
+            .iter()
+            .copied()
+            .find(|&m| is_castling(m) && to_square(m) % 8 == file)
+            .ok_or_else(|| SanError::Illegal(text.to_string()));
+    }
+
+    // Capture and separator marks carry no information we need
+    let mut chars: Vec<char> = trimmed.chars().filter(|&c| !matches!(c, 'x' | 'X' | ':' | '-')).collect();
+
+    let piece = match chars.first() {
+        Some('N') => Piece::Knight,
+        Some('B') => Piece::Bishop,
+        Some('R') => Piece::Rook,
+        Some('Q') => Piece::Queen,
+        Some('K') => Piece::King,
+        Some(_) => Piece::Pawn,
+        None => return Err(malformed()),
+    };
+    if piece != Piece::Pawn {
+        chars.remove(0);
+    }
+
+    // Promotion piece after the destination, with or without '='
+    let mut promo = PROMO_NONE;
+    if let Some(&last) = chars.last()
+        && last.is_ascii_alphabetic()
+    {
+        promo = match last.to_ascii_uppercase() {
+            'N' => PROMO_N,
+            'B' => PROMO_B,
+            'R' => PROMO_R,
+            'Q' => PROMO_Q,
+            _ => return Err(malformed()),
+        };
+        chars.pop();
+        if chars.last() == Some(&'=') {
+            chars.pop();
+        }
+    }
+
+    if chars.len() < 2 {
+        return Err(malformed());
+    }
+    let destination: String = chars.split_off(chars.len() - 2).into_iter().collect();
+    let to = coord_to_square(&destination).ok_or_else(malformed)?;
+
+    // Whatever is left narrows down the from square
+    let mut from_file = None;
+    let mut from_rank = None;
+    for c in chars {
+        match c {
+            'a'..='h' => from_file = Some(c as u8 - b'a'),
+            '1'..='8' => from_rank = Some(c as u8 - b'1'),
+            _ => return Err(malformed()),
+        }
+    }
+
+    let candidates: Vec<Move> = moves
+        .iter()
+        .copied()
+        .filter(|&m| !is_castling(m) && to_square(m) == to.0 && promo_piece(m) == promo)
+        .filter(|&m| board.piece_at(Square(from_square(m))).map(|(p, _)| p) == Some(piece))
+        .filter(|&m| from_file.is_none_or(|file| from_square(m) % 8 == file))
+        .filter(|&m| from_rank.is_none_or(|rank| from_square(m) / 8 == rank))
+        .collect();
+
+    match candidates.as_slice() {
+        [m] => Ok(*m),
+        [] => Err(SanError::Illegal(text.to_string())),
+        _ => Err(SanError::Ambiguous(text.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::board::START_FEN;
+
+    fn board(fen: &str) -> Board {
+        Board::from_fen(fen).expect("valid FEN")
+    }
+
+    // Every legal move of the position, in SAN, sorted
+    fn all_san(fen: &str) -> Vec<String> {
+        let board = board(fen);
+        let mut san: Vec<String> = generate_legal_moves(&board).iter().map(|&m| move_to_san(&board, m)).collect();
+        san.sort();
+        san
+    }
+
+    #[test]
+    fn start_position() {
+        let san = all_san(START_FEN);
+
+        assert_eq!(san.len(), 20);
+        assert!(san.contains(&"e4".to_string()));
+        assert!(san.contains(&"Nf3".to_string()));
+        assert!(san.contains(&"Na3".to_string()));
+    }
+
+    #[test]
+    fn disambiguation_by_file_rank_or_both() {
+        // Knights on b1 and f1 both reach d2, rooks on a1 and a5 both reach a3
+        let san = all_san("7k/8/8/R7/8/8/8/RN1K1N2 w - - 0 1");
+        assert!(san.contains(&"Nbd2".to_string()));
+        assert!(san.contains(&"Nfd2".to_string()));
+        assert!(san.contains(&"R1a3".to_string()));
+        assert!(san.contains(&"R5a3".to_string()));
+        assert!(san.contains(&"Nh2".to_string()));
+
+        // Queens on d1, d5 and h5 all reach h1
+        let san = all_san("8/k7/8/3Q3Q/8/K7/8/3Q4 w - - 0 1");
+        assert!(san.contains(&"Q1h1".to_string()));
+        assert!(san.contains(&"Qhh1".to_string()));
+        assert!(san.contains(&"Qd5h1".to_string()));
+    }
+
+    #[test]
+    fn captures_promotions_and_checks() {
+        let fen = "r3k3/1P6/8/3pP3/8/8/8/4K2R w K d6 0 1";
+        let san = all_san(fen);
+
+        assert!(san.contains(&"exd6".to_string()));
+        assert!(san.contains(&"bxa8=Q+".to_string()));
+        assert!(san.contains(&"b8=N".to_string()));
+        assert!(san.contains(&"O-O".to_string()));
+        assert!(san.contains(&"Rh8+".to_string()));
+
+        // Fool's mate
+        let board = board("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2");
+        let mate = parse_san(&board, "Qh4").unwrap();
+        assert_eq!(move_to_san(&board, mate), "Qh4#");
+        assert_eq!(mate.to_san(&board), "Qh4#");
+    }
+
+    #[test]
+    fn round_trip() {
+        for fen in [
+            START_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        ] {
+            let board = board(fen);
+            for &m in generate_legal_moves(&board).iter() {
+                let san = move_to_san(&board, m);
+                assert_eq!(parse_san(&board, &san), Ok(m), "{} in {}", san, fen);
+            }
+        }
+    }
+
+    #[test]
+    fn sloppy_input() {
+        let board = board("r3k3/1P6/8/3pP3/8/8/8/4K2R w K d6 0 1");
+        let parse = |text| parse_san(&board, text).map(|m| move_to_san(&board, m));
+
+        assert_eq!(parse("ed6"), Ok("exd6".to_string()));
+        assert_eq!(parse("exd6 e.p."), Ok("exd6".to_string()));
+        assert_eq!(parse("0-0"), Ok("O-O".to_string()));
+        assert_eq!(parse("b8Q"), Ok("b8=Q+".to_string()));
+        assert_eq!(parse("b8q"), Ok("b8=Q+".to_string()));
+        assert_eq!(parse("ba8=Q"), Ok("bxa8=Q+".to_string()));
+        assert_eq!(parse("Rh8+!?"), Ok("Rh8+".to_string()));
+        assert_eq!(parse("Rh1-h8"), Ok("Rh8+".to_string()));
+    }
+
+    #[test]
+    fn rejects_bad_moves() {
+        let start = board(START_FEN);
+
+        assert_eq!(parse_san(&start, "e5"), Err(SanError::Illegal("e5".to_string())));
+        assert_eq!(parse_san(&start, "O-O"), Err(SanError::Illegal("O-O".to_string())));
+        assert_eq!(parse_san(&start, "Zf3"), Err(SanError::Malformed("Zf3".to_string())));
+        assert_eq!(parse_san(&start, ""), Err(SanError::Malformed("".to_string())));
+
+        let rooks = board("k7/8/8/8/8/8/4K3/R6R w - - 0 1");
+        assert_eq!(parse_san(&rooks, "Rd1"), Err(SanError::Ambiguous("Rd1".to_string())));
+        assert!(parse_san(&rooks, "Rad1").is_ok());
+
+        // A promotion must say what it promotes to
+        let promotion = board("8/4P3/8/8/8/8/k7/4K3 w - - 0 1");
+        assert_eq!(parse_san(&promotion, "e8"), Err(SanError::Illegal("e8".to_string())));
+    }
+}
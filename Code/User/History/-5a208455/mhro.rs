@@ -0,0 +1,120 @@
+use crate::state::board::{Board, CastlingRights, Color, Piece, Square};
+
+// Fixed seed so keys (and therefore hashes) are identical on every run
+const SEED: u64 = 0x3243_F6A8_885A_308D;
+
+/// Random keys for every hashed feature of a position
+pub struct Zobrist {
+    pub pieces: [[[u64; 64]; 6]; 2], // [color][piece_type][square]
+    pub side: u64,                   // xored in when black is to move
+    pub castling: [u64; 16],         // indexed by CastlingRights::index
+    pub en_passant: [u64; 8],        // by file of the en passant square
+}
+
+pub static ZOBRIST: Zobrist = Zobrist::new();
+
+// SplitMix64 step: returns (next state, output)
+const fn split_mix(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (state, z ^ (z >> 31))
+}
+
+impl Zobrist {
+    const fn new() -> Self {
+        let mut state = SEED;
+        let mut pieces = [[[0u64; 64]; 6]; 2];
+        let mut castling = [0u64; 16];
+        let mut en_passant = [0u64; 8];
+
+        let mut color = 0;
+        while color < 2 {
+            let mut piece = 0;
+            while piece < 6 {
+                let mut sq = 0;
+                while sq < 64 {
+                    let (next, key) = split_mix(state);
+                    state = next;
+                    pieces[color][piece][sq] = key;
+                    sq += 1;
+                }
+                piece += 1;
+            }
+            color += 1;
+        }
+
+        let (next, side) = split_mix(state);
+        state = next;
+
+        // No rights at all hashes to nothing
+        let mut i = 1;
+        while i < 16 {
+            let (next, key) = split_mix(state);
+            state = next;
+            castling[i] = key;
+            i += 1;
+        }
+
+        let mut file = 0;
+        while file < 8 {
+            let (next, key) = split_mix(state);
+            state = next;
+            en_passant[file] = key;
+            file += 1;
+        }
+
+        Self {
+            pieces,
+            side,
+            castling,
+            en_passant,
+        }
+    }
+
+    pub fn piece(&self, piece: Piece, color: Color, sq: Square) -> u64 {
+        self.pieces[color as usize][piece as usize][sq.0 as usize]
+    }
+
+    pub fn castling(&self, rights: CastlingRights) -> u64 {
+        self.castling[rights.index()]
+    }
+
+    pub fn en_passant(&self, ep: Option<Square>) -> u64 {
+        match ep {
+            Some(sq) => self.en_passant[(sq.0 % 8) as usize],
+            None => 0,
+        }
+    }
+}
+
+/// Full position key computed from scratch (the incremental key must always match this)
+pub fn compute_hash(board: &Board) -> u64 {
+    let mut hash = 0;
+
+    for sq in 0..64 {
+        if let Some((piece, color)) = board.pieces[sq] {
+            hash ^= ZOBRIST.piece(piece, color, Square(sq as u8));
+        }
+    }
+
+    if board.side_to_move == Color::Black {
+        hash ^= ZOBRIST.side;
+    }
+
+    hash ^ ZOBRIST.castling(board.castling) ^ ZOBRIST.en_passant(board.en_passant)
+}
+
+/// Key over pawns only, for caching pawn-structure evaluation
+pub fn compute_pawn_hash(board: &Board) -> u64 {
+    let mut hash = 0;
+
+    for sq in 0..64 {
+        if let Some((Piece::Pawn, color)) = board.pieces[sq] {
+            hash ^= ZOBRIST.piece(Piece::Pawn, color, Square(sq as u8));
+        }
+    }
+
+    hash
+}
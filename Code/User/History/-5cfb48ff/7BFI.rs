@@ -0,0 +1,137 @@
+use crate::pgn::{move_number, play, Game, PgnError, Variation, SEVEN_TAG_ROSTER};
+use crate::state::board::{Board, Color};
+use crate::state::san::move_to_san;
+
+// Export format keeps movetext lines under 80 columns
+const MAX_LINE: usize = 79;
+
+/// A game in PGN export format: the seven tag roster first (with placeholders for any that
+/// are missing), the other tags after it, then the movetext wrapped at 79 columns
+///
+/// The moves are replayed as they are written, so an illegal move is an error rather than
+/// garbage in the output.
+pub fn write_game(game: &Game) -> Result<String, PgnError> {
+    let mut pgn = String::new();
+
+    for name in SEVEN_TAG_ROSTER {
+        let value = match (game.tag(name), name) {
+            (Some(value), _) => value,
+            (None, "Date") => "????.??.??",
+            (None, "Result") => "*",
+            (None, _) => "?",
+        };
+        pgn.push_str(&tag_pair(name, value));
+    }
+    for (name, value) in &game.tags {
+        if !SEVEN_TAG_ROSTER.contains(&name.as_str()) {
+            pgn.push_str(&tag_pair(name, value));
+        }
+    }
+    pgn.push('\n');
+
+    let mut tokens = Vec::new();
+    write_variation(&mut tokens, game.start_board()?, &game.mainline)?;
+    tokens.push(game.result().to_string());
+
+    for line in wrap(&tokens) {
+        pgn.push_str(&line);
+        pgn.push('\n');
+    }
+    pgn.push('\n');
+
+    Ok(pgn)
+}
+
+fn tag_pair(name: &str, value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("[{} \"{}\"]\n", name, escaped)
+}
+
+// Movetext tokens for a line of moves starting at `board`
+fn write_variation(tokens: &mut Vec<String>, mut board: Board, variation: &Variation) -> Result<(), PgnError> {
+    if let Some(comment) = &variation.comment {
+        write_comment(tokens, comment);
+    }
+
+    // Black's moves only get a number ("12...") where the flow of moves was interrupted
+    let mut numbered = false;
+
+    for node in &variation.moves {
+        if board.side_to_move == Color::White || !numbered {
+            tokens.push(move_number(&board));
+        }
+
+        let before = board.clone();
+        let san = move_to_san(&board, node.mov);
+        play(&mut board, node.mov)?;
+
+        tokens.push(san);
+        tokens.extend(node.nags.iter().map(|nag| format!("${}", nag)));
+        numbered = true;
+
+        if let Some(comment) = &node.comment {
+            write_comment(tokens, comment);
+            numbered = false;
+        }
+
+        for side_line in &node.variations {
+            tokens.push("(".to_string());
+            write_variation(tokens, before.clone(), side_line)?;
+            tokens.push(")".to_string());
+            numbered = false;
+        }
+    }
+
+    Ok(())
+}
+
+// One token per word, so long comments wrap like everything else
+fn write_comment(tokens: &mut Vec<String>, comment: &str) {
+    let words: Vec<&str> = comment.split_whitespace().collect();
+
+    match words.as_slice() {
+        [] => tokens.push("{}".to_string()),
+        [word] => tokens.push(format!("{{{}}}", word)),
+        [first, middle @ .., last] => {
+            tokens.push(format!("{{{}", first));
+            tokens.extend(middle.iter().map(|word| word.to_string()));
+            tokens.push(format!("{}}}", last));
+        }
+    }
+}
+
+// Join tokens with single spaces into lines of at most MAX_LINE columns. Parentheses
+// hug the token next to them: "(12... Nf6 13. Bd3)".
+fn wrap(tokens: &[String]) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut open = String::new();
+
+    for token in tokens {
+        match token.as_str() {
+            "(" => open.push('('),
+            ")" => match words.last_mut() {
+                Some(last) if open.is_empty() => last.push(')'),
+                _ => open.push(')'),
+            },
+            _ => words.push(format!("{}{}", std::mem::take(&mut open), token)),
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in words {
+        if !line.is_empty() && line.len() + 1 + word.len() > MAX_LINE {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
@@ -0,0 +1,86 @@
+// Game-ending rules, checked through the `Result:` line of the UCI `d` command.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn result(position: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run chess-engine");
+
+    let commands = format!("position {}\nd\nquit\n", position);
+    child.stdin.take().unwrap().write_all(commands.as_bytes()).unwrap();
+
+    let output = child.wait_with_output().expect("chess-engine did not finish");
+    assert!(output.status.success(), "chess-engine exited with {}", output.status);
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("Result: "))
+        .expect("no result in d output")
+        .to_string()
+}
+
+#[test]
+fn game_in_progress() {
+    assert_eq!(result("startpos"), "* (game in progress)");
+    assert_eq!(result("startpos moves e2e4 e7e5"), "* (game in progress)");
+}
+
+#[test]
+fn checkmate() {
+    assert_eq!(result("startpos moves f2f3 e7e5 g2g4 d8h4"), "0-1 (black mates)");
+    assert_eq!(result("fen 7k/6Q1/6K1/8/8/8/8/8 b - - 0 1"), "1-0 (white mates)");
+}
+
+#[test]
+fn stalemate() {
+    assert_eq!(result("fen 7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"), "1/2-1/2 (stalemate)");
+}
+
+#[test]
+fn threefold_repetition() {
+    // The start position comes round a second time, then a third
+    let twice = "startpos moves g1f3 g8f6 f3g1 f6g8";
+    assert_eq!(result(twice), "* (game in progress)");
+    assert_eq!(result(&format!("{} g1f3 g8f6 f3g1 f6g8", twice)), "1/2-1/2 (threefold repetition)");
+
+    // A pawn move in between means nothing before it can repeat
+    let reset = "startpos moves g1f3 g8f6 f3g1 f6g8 e2e3 e7e6 g1f3 g8f6 f3g1 f6g8";
+    assert_eq!(result(reset), "* (game in progress)");
+}
+
+#[test]
+fn fifty_move_rule() {
+    assert_eq!(result("fen 8/8/8/4k3/8/8/3K4/7R w - - 99 80"), "* (game in progress)");
+    assert_eq!(result("fen 8/8/8/4k3/8/8/3K4/7R w - - 99 80 moves h1h2"), "1/2-1/2 (fifty-move rule)");
+
+    // Mate on the hundredth half-move still counts as mate
+    assert_eq!(result("fen 7k/8/6K1/8/8/8/8/R7 w - - 99 80 moves a1a8"), "1-0 (white mates)");
+}
+
+#[test]
+fn insufficient_material() {
+    let drawn = "1/2-1/2 (insufficient material)";
+
+    assert_eq!(result("fen 8/8/8/4k3/8/8/3K4/8 w - - 0 1"), drawn);
+    assert_eq!(result("fen 8/8/8/4k3/8/8/3KN3/8 w - - 0 1"), drawn);
+    assert_eq!(result("fen 8/8/8/4k3/8/8/3K4/6b1 w - - 0 1"), drawn);
+
+    // Bishops that all live on light squares
+    assert_eq!(result("fen 8/8/8/4k3/8/3b4/3KB3/8 w - - 0 1"), drawn);
+    assert_eq!(result("fen 8/8/8/1b2k3/8/3b4/3KB3/8 w - - 0 1"), drawn);
+}
+
+#[test]
+fn mating_material_remains() {
+    let ongoing = "* (game in progress)";
+
+    // Bishops on opposite colours, two knights, knight against bishop, a lone pawn
+    assert_eq!(result("fen 8/8/8/4k3/8/4b3/3KB3/8 w - - 0 1"), ongoing);
+    assert_eq!(result("fen 8/8/8/4k3/8/8/3KNN2/8 w - - 0 1"), ongoing);
+    assert_eq!(result("fen 8/8/8/4k3/8/3b4/3KN3/8 w - - 0 1"), ongoing);
+    assert_eq!(result("fen 8/8/8/4k3/8/8/3KP3/8 w - - 0 1"), ongoing);
+}
@@ -0,0 +1,59 @@
+// Endgame scaling, checked through the `Eval:` line of the UCI `d` command.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// Static evaluation of a FEN, from the side to move's point of view
+fn eval(fen: &str) -> i32 {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run chess-engine");
+
+    let commands = format!("position fen {}\nd\nquit\n", fen);
+    child.stdin.take().unwrap().write_all(commands.as_bytes()).unwrap();
+
+    let output = child.wait_with_output().expect("chess-engine did not finish");
+    assert!(output.status.success(), "chess-engine exited with {}", output.status);
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("Eval: "))
+        .expect("no eval in d output")
+        .parse()
+        .expect("eval is not a number")
+}
+
+#[test]
+fn insufficient_material_is_zero() {
+    assert_eq!(eval("8/8/4k3/8/8/3BK3/8/8 w - - 0 1"), 0);
+    assert_eq!(eval("8/8/4k3/8/8/4K3/8/6N1 b - - 0 1"), 0);
+    assert_eq!(eval("8/2b5/4k3/8/8/4K3/8/B7 w - - 0 1"), 0);
+}
+
+#[test]
+fn wrong_rook_pawn() {
+    // The a1 bishop can't cover a8 and the black king sits there: a dead draw
+    assert_eq!(eval("k7/8/8/8/8/8/P7/B6K w - - 0 1"), 0);
+    assert_eq!(eval("k7/8/8/8/8/8/P7/B6K b - - 0 1"), 0);
+    assert_eq!(eval("8/8/8/8/8/8/k6p/4b2K w - - 0 1"), 0);
+
+    // A bishop that controls the queening square wins
+    assert!(eval("k7/8/8/8/8/8/P7/1B5K w - - 0 1") > 200);
+    assert!(eval("8/8/8/8/8/8/k6p/5b1K b - - 0 1") > 200);
+
+    // So does the wrong bishop while the defending king is still far from the corner
+    assert!(eval("8/8/8/4k3/8/8/P7/B6K w - - 0 1") > 200);
+}
+
+#[test]
+fn opposite_bishops_scale_down() {
+    // Two pawns up with bishops on opposite colours (c1 dark, f7 light) against the same
+    // material with both bishops on dark squares (c1, e7)
+    let opposite = eval("4k3/5b2/8/8/8/8/PPP5/2B1K3 w - - 0 1");
+    let same = eval("4k3/4b3/8/8/8/8/PPP5/2B1K3 w - - 0 1");
+
+    assert!(opposite > 0, "{}", opposite);
+    assert!(2 * opposite < same, "opposite {} vs same {}", opposite, same);
+}
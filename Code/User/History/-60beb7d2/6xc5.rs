@@ -0,0 +1,8 @@
+pub mod board;
+pub mod r#move;
+pub mod state;
+pub mod make_move;
+pub mod null_move;
+pub mod undo_move;
+pub mod game_result;
+pub mod zobrist;
@@ -0,0 +1,130 @@
+pub mod reader;
+pub mod writer;
+
+use std::fmt;
+use std::io;
+
+use crate::movegen::generate::generate_legal_moves;
+use crate::state::board::{Board, Color, FenError, START_FEN};
+use crate::state::make_move::make_move;
+use crate::state::r#move::{move_to_uci, Move};
+use crate::state::san::SanError;
+use crate::state::state::GameState;
+
+pub use self::reader::PgnReader;
+pub use self::writer::write_game;
+
+/// Tags every PGN game carries, in the order they are exported
+pub const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+/// One game: its tag pairs and the moves played, with any annotations and side lines
+#[derive(Clone, Default, Debug)]
+pub struct Game {
+    pub tags: Vec<(String, String)>, // in file order, roster tags included
+    pub mainline: Variation,
+}
+
+/// A sequence of moves from some position, main line or side line
+#[derive(Clone, Default, Debug)]
+pub struct Variation {
+    pub comment: Option<String>, // before the first move
+    pub moves: Vec<PgnMove>,
+}
+
+/// A move of a game with what was written after it
+#[derive(Clone, Debug)]
+pub struct PgnMove {
+    pub mov: Move,
+    pub nags: Vec<u8>,
+    pub comment: Option<String>,
+    pub variations: Vec<Variation>, // alternatives to this move, from the position before it
+}
+
+impl PgnMove {
+    pub fn new(mov: Move) -> Self {
+        Self {
+            mov,
+            nags: Vec::new(),
+            comment: None,
+            variations: Vec::new(),
+        }
+    }
+}
+
+impl Game {
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Set a tag, keeping its place if it is already there
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Game termination marker: 1-0, 0-1, 1/2-1/2 or * (unknown or still going)
+    pub fn result(&self) -> &str {
+        self.tag("Result").unwrap_or("*")
+    }
+
+    /// The position the game starts from: the FEN tag if there is one, else the usual start
+    pub fn start_board(&self) -> Result<Board, PgnError> {
+        let fen = self.tag("FEN").unwrap_or(START_FEN);
+        Board::from_fen(fen).map_err(PgnError::Fen)
+    }
+}
+
+// Make a move with `make_move` after checking it is legal in `board`
+fn play(board: &mut Board, mov: Move) -> Result<(), PgnError> {
+    if !generate_legal_moves(board).iter().any(|&m| m == mov) {
+        return Err(PgnError::IllegalMove {
+            at: move_number(board),
+            mov: move_to_uci(mov),
+        });
+    }
+
+    make_move(board, mov, &mut GameState::new());
+    Ok(())
+}
+
+// Move number as written before the move about to be played: "12." or "12..."
+fn move_number(board: &Board) -> String {
+    match board.side_to_move {
+        Color::White => format!("{}.", board.fullmove_number),
+        Color::Black => format!("{}...", board.fullmove_number),
+    }
+}
+
+/// Reasons a game can't be read or written
+#[derive(Debug)]
+pub enum PgnError {
+    Io(io::Error),
+    Tag(String),                             // a tag pair line that didn't parse
+    Fen(FenError),                           // FEN tag with a bad position
+    San { at: String, error: SanError },     // move text that isn't a legal move
+    IllegalMove { at: String, mov: String }, // a stored move that isn't legal
+    Syntax(String),
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PgnError::Io(e) => write!(f, "{}", e),
+            PgnError::Tag(line) => write!(f, "malformed tag pair '{}'", line),
+            PgnError::Fen(e) => write!(f, "invalid FEN tag: {}", e),
+            PgnError::San { at, error } => write!(f, "at {} {}", at, error),
+            PgnError::IllegalMove { at, mov } => write!(f, "at {} illegal move {}", at, mov),
+            PgnError::Syntax(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+impl From<io::Error> for PgnError {
+    fn from(e: io::Error) -> Self {
+        PgnError::Io(e)
+    }
+}
@@ -0,0 +1,221 @@
+use crate::movegen::generate::generate_legal_moves;
+use crate::search::MAX_PLY;
+use crate::see::see;
+use crate::state::board::{Board, Color, Piece, Square};
+use crate::state::r#move::{
+    from_square, is_en_passant, is_promotion, move_score, promo_piece, to_square, with_score,
+    without_score, Move,
+};
+
+// History scores stay within +-HISTORY_MAX, which maps onto the 12-bit ordering score
+const HISTORY_MAX: i32 = 16_384;
+
+/// Quiet-move ordering knowledge gathered during a search: killers, butterfly history
+/// and countermoves
+pub struct Heuristics {
+    killers: [[Move; 2]; MAX_PLY],
+    history: [[[i32; 64]; 64]; 2],      // [color][from][to]
+    countermoves: [[[Move; 64]; 6]; 2], // [color][piece][to] of the move being answered
+}
+
+impl Heuristics {
+    pub fn new() -> Self {
+        Self {
+            killers: [[0; 2]; MAX_PLY],
+            history: [[[0; 64]; 64]; 2],
+            countermoves: [[[0; 64]; 6]; 2],
+        }
+    }
+
+    /// Reward a quiet move that caused a beta cutoff and penalise the quiets tried before it
+    pub fn update(&mut self, board: &Board, ply: usize, depth: u32, best: Move, tried: &[Move], previous: Option<Move>) {
+        let color = board.side_to_move as usize;
+        let bonus = (depth * depth).min(400) as i32;
+
+        if self.killers[ply][0] != best {
+            self.killers[ply][1] = self.killers[ply][0];
+            self.killers[ply][0] = best;
+        }
+
+        for &m in tried {
+            let entry = &mut self.history[color][from_square(m) as usize][to_square(m) as usize];
+            let delta = if m == best { bonus } else { -bonus };
+
+            // Gravity: the closer to the limit, the smaller the step
+            *entry += delta - *entry * delta.abs() / HISTORY_MAX;
+        }
+
+        if let Some(previous) = previous
+            && let Some((piece, _)) = board.piece_at(Square(to_square(previous)))
+        {
+            self.countermoves[color][piece as usize][to_square(previous) as usize] = best;
+        }
+    }
+
+    fn history(&self, color: Color, m: Move) -> i32 {
+        self.history[color as usize][from_square(m) as usize][to_square(m) as usize]
+    }
+
+    fn countermove(&self, board: &Board, previous: Option<Move>) -> Move {
+        let Some(previous) = previous else { return 0 };
+
+        match board.piece_at(Square(to_square(previous))) {
+            Some((piece, _)) => {
+                self.countermoves[board.side_to_move as usize][piece as usize][to_square(previous) as usize]
+            }
+            None => 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Stage {
+    TtMove,
+    Partition,
+    GoodCaptures,
+    Promotions,
+    Killer(usize),
+    Countermove,
+    ScoreQuiets,
+    Quiets,
+    BadCaptures,
+    Done,
+}
+
+/// Hands out the legal moves of a position best-first, in stages:
+/// TT move, winning and equal captures (MVV-LVA), promotions, two killers, the countermove,
+/// quiets by history and finally the captures that lose material
+///
+/// Ordering scores live in bits 20-31 of the moves while they wait; every move handed out
+/// has them stripped.
+pub struct MovePicker {
+    stage: Stage,
+    tt_move: Option<Move>,
+    killers: [Move; 2],
+    countermove: Move,
+
+    moves: Vec<Move>,
+    good_captures: Vec<Move>,
+    bad_captures: Vec<Move>,
+    promotions: Vec<Move>,
+    quiets: Vec<Move>,
+}
+
+impl MovePicker {
+    pub fn new(board: &Board, heuristics: &Heuristics, ply: usize, tt_move: Option<Move>, previous: Option<Move>) -> Self {
+        Self {
+            stage: Stage::TtMove,
+            tt_move,
+            killers: heuristics.killers[ply],
+            countermove: heuristics.countermove(board, previous),
+            moves: generate_legal_moves(board).as_slice().to_vec(),
+            good_captures: Vec::new(),
+            bad_captures: Vec::new(),
+            promotions: Vec::new(),
+            quiets: Vec::new(),
+        }
+    }
+
+    pub fn next(&mut self, board: &Board, heuristics: &Heuristics) -> Option<Move> {
+        loop {
+            match self.stage {
+                Stage::TtMove => {
+                    self.stage = Stage::Partition;
+
+                    if let Some(tt_move) = self.tt_move
+                        && let Some(i) = self.moves.iter().position(|&m| m == tt_move)
+                    {
+                        return Some(self.moves.swap_remove(i));
+                    }
+                }
+                Stage::Partition => {
+                    self.partition(board);
+                    self.stage = Stage::GoodCaptures;
+                }
+                Stage::GoodCaptures => match pick_best(&mut self.good_captures) {
+                    Some(m) => return Some(m),
+                    None => self.stage = Stage::Promotions,
+                },
+                Stage::Promotions => match pick_best(&mut self.promotions) {
+                    Some(m) => return Some(m),
+                    None => self.stage = Stage::Killer(0),
+                },
+                Stage::Killer(i) => {
+                    self.stage = if i == 0 { Stage::Killer(1) } else { Stage::Countermove };
+
+                    if let Some(m) = take(&mut self.quiets, self.killers[i]) {
+                        return Some(m);
+                    }
+                }
+                Stage::Countermove => {
+                    self.stage = Stage::ScoreQuiets;
+
+                    if let Some(m) = take(&mut self.quiets, self.countermove) {
+                        return Some(m);
+                    }
+                }
+                Stage::ScoreQuiets => {
+                    let color = board.side_to_move;
+                    for m in self.quiets.iter_mut() {
+                        let score = (heuristics.history(color, *m) + HISTORY_MAX) >> 3;
+                        *m = with_score(*m, score as u32);
+                    }
+                    self.stage = Stage::Quiets;
+                }
+                Stage::Quiets => match pick_best(&mut self.quiets) {
+                    Some(m) => return Some(m),
+                    None => self.stage = Stage::BadCaptures,
+                },
+                Stage::BadCaptures => match pick_best(&mut self.bad_captures) {
+                    Some(m) => return Some(m),
+                    None => self.stage = Stage::Done,
+                },
+                Stage::Done => return None,
+            }
+        }
+    }
+
+    // Sort the remaining moves into their stages, scoring the captures and promotions
+    fn partition(&mut self, board: &Board) {
+        for &m in &self.moves {
+            let victim = if is_en_passant(m) {
+                Some(Piece::Pawn)
+            } else {
+                board.piece_at(Square(to_square(m))).map(|(piece, _)| piece)
+            };
+
+            match victim {
+                Some(victim) => {
+                    let attacker = board.piece_at(Square(from_square(m))).map_or(0, |(piece, _)| piece as u32);
+                    let mvv_lva = (victim as u32 + 1) * 8 - attacker + promo_piece(m);
+
+                    if see(board, m) >= 0 {
+                        self.good_captures.push(with_score(m, mvv_lva));
+                    } else {
+                        self.bad_captures.push(with_score(m, mvv_lva));
+                    }
+                }
+                None if is_promotion(m) => self.promotions.push(with_score(m, promo_piece(m))),
+                None => self.quiets.push(m),
+            }
+        }
+
+        self.moves.clear();
+    }
+}
+
+// Remove and return the highest-scoring move
+fn pick_best(moves: &mut Vec<Move>) -> Option<Move> {
+    let (best, _) = moves.iter().enumerate().max_by_key(|&(_, &m)| move_score(m))?;
+    Some(without_score(moves.swap_remove(best)))
+}
+
+// Remove `target` from `moves` if it is there (moves in the list carry no score yet)
+fn take(moves: &mut Vec<Move>, target: Move) -> Option<Move> {
+    if target == 0 {
+        return None;
+    }
+
+    let i = moves.iter().position(|&m| m == target)?;
+    Some(moves.swap_remove(i))
+}
@@ -0,0 +1,240 @@
+use std::time::{Duration, Instant};
+
+use crate::state::r#move::Move;
+
+pub const DEFAULT_MOVE_OVERHEAD: u64 = 10;
+pub const MAX_MOVE_OVERHEAD: u64 = 5000;
+
+// Moves we assume are still to be played when the GUI doesn't say (sudden death)
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+
+// Never plan for more than this many moves ahead, even in long repeating controls
+const MAX_MOVES_TO_GO: u32 = 50;
+
+// How far the hard limit may stretch past the soft one
+const HARD_SOFT_RATIO: u32 = 4;
+
+// Soft-limit scale by how many iterations in a row kept the same best move
+const STABILITY: [f64; 6] = [1.8, 1.4, 1.15, 1.0, 0.9, 0.8];
+
+/// The clock situation sent with `go`, for the side to move
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Clock {
+    pub time: Option<u64>,
+    pub inc: u64,
+    pub movestogo: Option<u32>,
+    pub movetime: Option<u64>,
+}
+
+/// Thinking time for one move: no new iteration starts past `soft`, the search aborts at `hard`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TimeLimits {
+    pub soft: Duration,
+    pub hard: Duration,
+}
+
+/// Work out the time limits for this move, or None when the search isn't timed
+///
+/// `overhead` is kept in reserve for the GUI and the network on every move.
+pub fn allocate(clock: &Clock, overhead: u64) -> Option<TimeLimits> {
+    if let Some(movetime) = clock.movetime {
+        let budget = Duration::from_millis(movetime.saturating_sub(overhead).max(1));
+        return Some(TimeLimits {
+            soft: budget,
+            hard: budget,
+        });
+    }
+
+    let time = clock.time?;
+    let moves_to_go = clock.movestogo.unwrap_or(DEFAULT_MOVES_TO_GO).clamp(1, MAX_MOVES_TO_GO) as u64;
+
+    // What we can spend without flagging, leaving the overhead for every remaining move
+    let available = time.saturating_sub(overhead * moves_to_go.min(5)).max(1);
+
+    // Even share of the clock, plus most of the increment we get back
+    let soft = (available / moves_to_go + clock.inc * 3 / 4).min(available * 3 / 4).max(1);
+    let hard = (soft * HARD_SOFT_RATIO as u64).min(available * 9 / 10).max(soft);
+
+    Some(TimeLimits {
+        soft: Duration::from_millis(soft),
+        hard: Duration::from_millis(hard),
+    })
+}
+
+/// Decides when to stop a timed search
+///
+/// The soft limit is stretched when the search looks unsettled (the best move keeps changing,
+/// the score is falling, the best move takes little of the effort) and shrunk when it doesn't.
+pub struct TimeManager {
+    start: Instant,
+    limits: Option<TimeLimits>,
+    best_move: Option<Move>,
+    stability: usize,
+    previous_score: Option<i32>,
+    scale: f64,
+}
+
+impl TimeManager {
+    pub fn new(start: Instant, limits: Option<TimeLimits>) -> Self {
+        Self {
+            start,
+            limits,
+            best_move: None,
+            stability: 0,
+            previous_score: None,
+            scale: 1.0,
+        }
+    }
+
+    /// The search must stop right now
+    pub fn hard_limit_reached(&self) -> bool {
+        match self.limits {
+            Some(limits) => self.start.elapsed() >= limits.hard,
+            None => false,
+        }
+    }
+
+    /// Record a finished iteration; returns true if no further iteration should be started
+    pub fn iteration_done(&mut self, best_move: Move, score: i32, best_move_nodes: u64, total_nodes: u64) -> bool {
+        self.update(best_move, score, best_move_nodes, total_nodes);
+
+        match self.optimum() {
+            Some(optimum) => self.start.elapsed() >= optimum,
+            None => false,
+        }
+    }
+
+    /// Current target time: the soft limit after scaling, never beyond the hard limit
+    pub fn optimum(&self) -> Option<Duration> {
+        self.limits.map(|limits| {
+            // A fixed time per move (movetime) is meant to be used in full
+            if limits.soft == limits.hard {
+                limits.hard
+            } else {
+                limits.soft.mul_f64(self.scale).min(limits.hard)
+            }
+        })
+    }
+
+    fn update(&mut self, best_move: Move, score: i32, best_move_nodes: u64, total_nodes: u64) {
+        // Best-move stability: consecutive iterations agreeing on the move
+        if self.best_move == Some(best_move) {
+            self.stability += 1;
+        } else {
+            self.stability = 0;
+        }
+        self.best_move = Some(best_move);
+        let stability = STABILITY[self.stability.min(STABILITY.len() - 1)];
+
+        // A falling score means trouble was found; spend up to twice as long sorting it out
+        let drop = self.previous_score.map_or(0, |previous| previous - score).clamp(0, 100);
+        let falling = 1.0 + drop as f64 / 100.0;
+        self.previous_score = Some(score);
+
+        // If the best move soaked up nearly all the effort the alternatives were easy to refute
+        let fraction = if total_nodes > 0 {
+            best_move_nodes as f64 / total_nodes as f64
+        } else {
+            0.5
+        };
+        let effort = (1.6 - fraction) * 1.2;
+
+        self.scale = (stability * falling * effort).clamp(0.3, 3.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(time: u64, inc: u64, movestogo: Option<u32>) -> TimeLimits {
+        let clock = Clock {
+            time: Some(time),
+            inc,
+            movestogo,
+            movetime: None,
+        };
+        allocate(&clock, DEFAULT_MOVE_OVERHEAD).expect("timed search")
+    }
+
+    #[test]
+    fn untimed_search_has_no_limits() {
+        assert_eq!(allocate(&Clock::default(), DEFAULT_MOVE_OVERHEAD), None);
+    }
+
+    #[test]
+    fn movetime_is_used_as_is_minus_overhead() {
+        let clock = Clock {
+            movetime: Some(1000),
+            ..Clock::default()
+        };
+        let limits = allocate(&clock, 10).unwrap();
+
+        assert_eq!(limits.soft, Duration::from_millis(990));
+        assert_eq!(limits.hard, Duration::from_millis(990));
+    }
+
+    #[test]
+    fn sudden_death_spreads_the_clock() {
+        let limits = limits(60_000, 0, None);
+
+        // Roughly a thirtieth of the clock, with room to extend but nowhere near flagging
+        assert!(limits.soft >= Duration::from_millis(1500) && limits.soft <= Duration::from_millis(2500));
+        assert!(limits.hard > limits.soft);
+        assert!(limits.hard <= Duration::from_millis(60_000 * 9 / 10));
+    }
+
+    #[test]
+    fn sudden_death_with_little_time_left_never_flags() {
+        for time in [1, 20, 50, 100, 500] {
+            let limits = limits(time, 0, None);
+
+            assert!(limits.soft >= Duration::from_millis(1));
+            assert!(limits.hard < Duration::from_millis(time.max(2)));
+        }
+    }
+
+    #[test]
+    fn increment_adds_to_the_budget() {
+        let without = limits(10_000, 0, None);
+        let with = limits(10_000, 1_000, None);
+
+        assert!(with.soft > without.soft);
+        assert!(with.soft >= Duration::from_millis(750));
+
+        // Even a big increment can't make us spend what isn't on the clock
+        let starved = limits(500, 5_000, None);
+        assert!(starved.hard < Duration::from_millis(500));
+    }
+
+    #[test]
+    fn repeating_control_uses_moves_to_go() {
+        // 40 moves in 5 minutes, at the start of the period
+        let limits_40 = limits(300_000, 0, Some(40));
+        assert!(limits_40.soft >= Duration::from_millis(7_000) && limits_40.soft <= Duration::from_millis(8_000));
+
+        // Last move before the time control: plenty to spend, but still not all of it
+        let last = limits(5_000, 0, Some(1));
+        assert!(last.soft > limits_40.soft / 4);
+        assert!(last.hard < Duration::from_millis(5_000));
+
+        // Fewer moves to go means more time per move
+        assert!(limits(60_000, 0, Some(5)).soft > limits(60_000, 0, Some(20)).soft);
+    }
+
+    #[test]
+    fn unstable_search_gets_more_time() {
+        let limits = limits(60_000, 0, None);
+        let mut stable = TimeManager::new(Instant::now(), Some(limits));
+        let mut unstable = TimeManager::new(Instant::now(), Some(limits));
+
+        for iteration in 0..6u32 {
+            stable.update(1, 20, 900, 1000);
+            unstable.update(iteration, 20 - iteration as i32 * 30, 300, 1000);
+        }
+
+        assert!(stable.optimum().unwrap() < limits.soft);
+        assert!(unstable.optimum().unwrap() > limits.soft);
+        assert!(unstable.optimum().unwrap() <= limits.hard);
+    }
+}
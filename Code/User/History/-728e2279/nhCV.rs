@@ -0,0 +1,34 @@
+use crate::state::board::Board;
+use crate::state::state::GameState;
+use crate::state::zobrist::{compute_hash, ZOBRIST};
+
+/// Hand the move to the opponent without moving anything (for null-move pruning)
+///
+/// Must not be called while in check: the opponent would be left able to take the king.
+pub fn make_null_move(board: &mut Board, state: &mut GameState) {
+    debug_assert!(!board.in_check(), "null move made while in check");
+
+    state.save(board, 0);
+
+    // Any en passant chance lapses once the turn has passed
+    board.hash ^= ZOBRIST.en_passant(board.en_passant);
+    board.en_passant = None;
+    board.halfmove_clock += 1;
+
+    board.side_to_move = board.side_to_move.opposite();
+    board.hash ^= ZOBRIST.side;
+
+    // We weren't in check, and in a legal position the opponent isn't either
+    board.checkers = 0;
+
+    debug_assert_eq!(board.hash, compute_hash(board), "incremental hash drifted");
+}
+
+pub fn undo_null_move(board: &mut Board, state: &GameState) {
+    board.side_to_move = board.side_to_move.opposite();
+    board.hash ^= ZOBRIST.side;
+
+    state.restore_state(board);
+
+    debug_assert_eq!(board.hash, compute_hash(board), "incremental hash drifted");
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+
+use crate::book::{encode_move, polyglot_key, BookEntry, BookError};
+use crate::pgn::PgnReader;
+use crate::state::board::Color;
+use crate::state::make_move::make_move;
+use crate::state::state::GameState;
+
+/// Which moves of a PGN collection make it into a book
+#[derive(Copy, Clone, Debug)]
+pub struct BuildOptions {
+    pub max_ply: usize, // half-moves of each game to take, from its start
+    pub min_games: u32, // a move must have been played this often in its position
+    pub min_score: f64, // percentage the side playing a move must have scored with it
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        Self {
+            max_ply: 30,
+            min_games: 3,
+            min_score: 0.0,
+        }
+    }
+}
+
+/// What went into a book, for the report printed after a build
+#[derive(Clone, Default, Debug)]
+pub struct BuildSummary {
+    pub games: usize,
+    pub skipped: usize, // unreadable games and games without a result
+    pub positions: usize,
+    pub entries: usize,
+}
+
+#[derive(Copy, Clone, Default)]
+struct MoveStats {
+    games: u32,
+    points: u32, // half-points for the side that played the move: 2 a win, 1 a draw
+}
+
+/// Replay the games of a PGN file and write the moves that pass `options` as a Polyglot
+/// book, entries sorted by key and then by weight
+///
+/// A move's weight is the half-points it scored, so the usual choice in a position is
+/// also the most successful one. Games that can't be read are reported and left out.
+pub fn build(pgn_path: &str, book_path: &str, options: &BuildOptions) -> Result<BuildSummary, BookError> {
+    let reader = PgnReader::new(BufReader::new(File::open(pgn_path)?));
+    let mut stats: HashMap<(u64, u16), MoveStats> = HashMap::new();
+    let mut summary = BuildSummary::default();
+
+    for (i, game) in reader.enumerate() {
+        let game = match game {
+            Ok(game) => game,
+            Err(e) => {
+                eprintln!("{}: game {}: {}", pgn_path, i + 1, e);
+                summary.skipped += 1;
+                continue;
+            }
+        };
+
+        // Half-points for white; unfinished games say nothing about the moves
+        let white_points = match game.result() {
+            "1-0" => 2,
+            "1/2-1/2" => 1,
+            "0-1" => 0,
+            _ => {
+                summary.skipped += 1;
+                continue;
+            }
+        };
+
+        // The reader has already replayed the game from this position
+        let mut board = game.start_board().expect("start position checked by the reader");
+        for node in game.mainline.moves.iter().take(options.max_ply) {
+            let entry = stats.entry((polyglot_key(&board), encode_move(node.mov))).or_default();
+            entry.games += 1;
+            entry.points += if board.side_to_move == Color::White { white_points } else { 2 - white_points };
+
+            make_move(&mut board, node.mov, &mut GameState::new());
+        }
+        summary.games += 1;
+    }
+
+    let mut entries: Vec<BookEntry> = stats
+        .into_iter()
+        .filter(|(_, stats)| stats.games >= options.min_games)
+        .filter(|(_, stats)| 50.0 * stats.points as f64 / stats.games as f64 >= options.min_score)
+        .map(|((key, mov), stats)| BookEntry {
+            key,
+            mov,
+            weight: stats.points.min(u16::MAX as u32) as u16,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key).then(b.weight.cmp(&a.weight)).then(a.mov.cmp(&b.mov)));
+
+    summary.entries = entries.len();
+    summary.positions = entries.chunk_by(|a, b| a.key == b.key).count();
+
+    let bytes: Vec<u8> = entries.iter().flat_map(|entry| entry.to_bytes()).collect();
+    fs::write(book_path, bytes)?;
+
+    Ok(summary)
+}
@@ -0,0 +1,222 @@
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+use crate::search::MATE_BOUND;
+use crate::state::r#move::{
+    encode_move, from_square, move_flag, promo_piece, to_square, Move, FLAG_PROMOTION, PROMO_NONE,
+};
+
+pub const DEFAULT_HASH_MB: usize = 16;
+pub const MAX_HASH_MB: usize = 65_536;
+
+// Age is kept in 6 bits next to the bound
+const AGE_MASK: u8 = 0x3F;
+
+/// How a stored score relates to the true value of the position
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Bound {
+    Exact,
+    Lower, // failed high: the score is at least this
+    Upper, // failed low: the score is at most this
+}
+
+/// A decoded table hit
+#[derive(Clone, Copy, Debug)]
+pub struct TtEntry {
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u8,
+    pub bound: Bound,
+}
+
+/// Shared transposition table
+///
+/// Each entry is one packed u64, so threads can read and write it without locks and
+/// never see a torn entry:
+///
+/// ```text
+/// bits  0-15  key verification (low 16 bits of the Zobrist key)
+/// bits 16-31  best move
+/// bits 32-47  score (i16)
+/// bits 48-55  depth
+/// bits 56-57  bound (0 = empty slot)
+/// bits 58-63  age
+/// ```
+///
+/// Buckets hold two entries: the first keeps the deepest result (unless it is from an
+/// older search), the second is always overwritten.
+pub struct TranspositionTable {
+    buckets: Vec<[AtomicU64; 2]>,
+    age: AtomicU8,
+}
+
+impl TranspositionTable {
+    /// Table using roughly `mb` megabytes
+    pub fn new(mb: usize) -> Self {
+        let bytes = mb.clamp(1, MAX_HASH_MB) * 1024 * 1024;
+        let count = (bytes / std::mem::size_of::<[AtomicU64; 2]>()).max(1);
+
+        Self {
+            buckets: (0..count).map(|_| [AtomicU64::new(0), AtomicU64::new(0)]).collect(),
+            age: AtomicU8::new(0),
+        }
+    }
+
+    /// Forget everything stored so far
+    pub fn clear(&self) {
+        for bucket in &self.buckets {
+            for slot in bucket {
+                slot.store(0, Ordering::Relaxed);
+            }
+        }
+        self.age.store(0, Ordering::Relaxed);
+    }
+
+    /// Start a new search: entries from earlier searches become preferred for replacement
+    pub fn new_search(&self) {
+        let age = (self.age.load(Ordering::Relaxed) + 1) & AGE_MASK;
+        self.age.store(age, Ordering::Relaxed);
+    }
+
+    /// Look up `key`, converting mate scores back to be relative to `ply`
+    pub fn probe(&self, key: u64, ply: usize) -> Option<TtEntry> {
+        let verification = key as u16;
+
+        self.bucket(key)
+            .iter()
+            .map(|slot| slot.load(Ordering::Relaxed))
+            .find(|&data| data != 0 && data as u16 == verification)
+            .and_then(unpack)
+            .map(|mut entry| {
+                entry.score = score_from_tt(entry.score, ply);
+                entry
+            })
+    }
+
+    /// Record a search result for `key`; mate scores are stored relative to this node
+    pub fn store(&self, key: u64, best_move: Option<Move>, score: i32, depth: u32, bound: Bound, ply: usize) {
+        let bucket = self.bucket(key);
+        let age = self.age.load(Ordering::Relaxed);
+        let depth = depth.min(u8::MAX as u32) as u8;
+
+        let deep = bucket[0].load(Ordering::Relaxed);
+        let same_position = deep != 0 && deep as u16 == key as u16;
+
+        // Don't lose the move of an earlier search of this position to a move-less result
+        let best_move = best_move.or_else(|| {
+            if same_position {
+                unpack(deep).and_then(|entry| entry.best_move)
+            } else {
+                None
+            }
+        });
+
+        let data = pack(key, best_move, score_to_tt(score, ply), depth, bound, age);
+
+        let deep_is_stale = ((deep >> 58) as u8) != age;
+        if deep == 0 || same_position || deep_is_stale || depth >= (deep >> 48) as u8 {
+            bucket[0].store(data, Ordering::Relaxed);
+        } else {
+            bucket[1].store(data, Ordering::Relaxed);
+        }
+    }
+
+    /// Permille of sampled entries written by the current search (UCI `hashfull`)
+    pub fn hashfull(&self) -> usize {
+        let age = self.age.load(Ordering::Relaxed);
+        let sample = &self.buckets[..self.buckets.len().min(500)];
+
+        let used = sample
+            .iter()
+            .flatten()
+            .map(|slot| slot.load(Ordering::Relaxed))
+            .filter(|&data| data != 0 && (data >> 58) as u8 == age)
+            .count();
+
+        used * 1000 / (sample.len() * 2)
+    }
+
+    fn bucket(&self, key: u64) -> &[AtomicU64; 2] {
+        // Multiply-shift maps the key onto any table size without a modulo
+        let index = ((key as u128 * self.buckets.len() as u128) >> 64) as usize;
+        &self.buckets[index]
+    }
+}
+
+fn pack(key: u64, best_move: Option<Move>, score: i32, depth: u8, bound: Bound, age: u8) -> u64 {
+    let bound_bits = match bound {
+        Bound::Exact => 1u64,
+        Bound::Lower => 2,
+        Bound::Upper => 3,
+    };
+
+    (key as u16 as u64)
+        | (best_move.map_or(0, compress_move) as u64) << 16
+        | (score as i16 as u16 as u64) << 32
+        | (depth as u64) << 48
+        | bound_bits << 56
+        | ((age & AGE_MASK) as u64) << 58
+}
+
+fn unpack(data: u64) -> Option<TtEntry> {
+    let bound = match (data >> 56) & 0x3 {
+        1 => Bound::Exact,
+        2 => Bound::Lower,
+        3 => Bound::Upper,
+        _ => return None,
+    };
+
+    let packed_move = (data >> 16) as u16;
+
+    Some(TtEntry {
+        best_move: (packed_move != 0).then(|| expand_move(packed_move)),
+        score: (data >> 32) as u16 as i16 as i32,
+        depth: (data >> 48) as u8,
+        bound,
+    })
+}
+
+// Squares take 12 bits; the top 4 hold the flag, or 8 + piece for a promotion
+// (flags only go up to 5, so the two never clash)
+fn compress_move(m: Move) -> u16 {
+    let kind = if move_flag(m) == FLAG_PROMOTION {
+        8 + promo_piece(m) - 1
+    } else {
+        move_flag(m)
+    };
+
+    (from_square(m) as u16) | (to_square(m) as u16) << 6 | (kind as u16) << 12
+}
+
+fn expand_move(packed: u16) -> Move {
+    let from = (packed & 0x3F) as u8;
+    let to = ((packed >> 6) & 0x3F) as u8;
+    let kind = (packed >> 12) as u32;
+
+    if kind >= 8 {
+        encode_move(from, to, FLAG_PROMOTION, kind - 8 + 1)
+    } else {
+        encode_move(from, to, kind, PROMO_NONE)
+    }
+}
+
+// Mate scores are stored as distance from this node rather than from the root,
+// so the entry stays valid wherever in the tree the position turns up again
+fn score_to_tt(score: i32, ply: usize) -> i32 {
+    if score >= MATE_BOUND {
+        score + ply as i32
+    } else if score <= -MATE_BOUND {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+fn score_from_tt(score: i32, ply: usize) -> i32 {
+    if score >= MATE_BOUND {
+        score - ply as i32
+    } else if score <= -MATE_BOUND {
+        score + ply as i32
+    } else {
+        score
+    }
+}
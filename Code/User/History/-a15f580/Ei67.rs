@@ -0,0 +1,76 @@
+// Opening book play through the OwnBook and BookFile UCI options.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+// Polyglot key of the start position and the move g2g4 in Polyglot's encoding
+const START_KEY: u64 = 0x463B96181691FC9C;
+const G2G4: u16 = 14 << 6 | 30;
+
+fn write_book(name: &str, entries: &[(u64, u16, u16)]) -> PathBuf {
+    let mut bytes = Vec::new();
+    for &(key, mov, weight) in entries {
+        bytes.extend(key.to_be_bytes());
+        bytes.extend(mov.to_be_bytes());
+        bytes.extend(weight.to_be_bytes());
+        bytes.extend(0u32.to_be_bytes());
+    }
+
+    let path = env::temp_dir().join(format!("chess-engine-{}-{}.bin", name, std::process::id()));
+    fs::write(&path, bytes).unwrap();
+    path
+}
+
+// Everything the engine prints for a script of UCI commands
+fn uci(commands: &str) -> (String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run chess-engine");
+
+    child.stdin.take().unwrap().write_all(format!("{}\nquit\n", commands).as_bytes()).unwrap();
+
+    let output = child.wait_with_output().expect("chess-engine did not finish");
+    assert!(output.status.success(), "chess-engine exited with {}", output.status);
+    (String::from_utf8_lossy(&output.stdout).to_string(), String::from_utf8_lossy(&output.stderr).to_string())
+}
+
+fn bestmove(stdout: &str) -> &str {
+    stdout.lines().find_map(|line| line.strip_prefix("bestmove ")).expect("no bestmove")
+}
+
+#[test]
+fn plays_book_moves_when_enabled() {
+    let book = write_book("own-book", &[(START_KEY, G2G4, 1)]);
+    let load = format!("setoption name BookFile value {}", book.display());
+
+    let enabled = format!("{}\nsetoption name OwnBook value true", load);
+    let (stdout, _) = uci(&format!("{}\nposition startpos\ngo depth 4", enabled));
+    assert_eq!(bestmove(&stdout), "g2g4");
+
+    // Off by default, and out of book the engine searches as usual
+    let (stdout, _) = uci(&format!("{}\nposition startpos\ngo depth 4", load));
+    assert_ne!(bestmove(&stdout), "g2g4");
+    let (stdout, _) = uci(&format!("{}\nposition startpos moves e2e4\ngo depth 4", enabled));
+    assert_ne!(bestmove(&stdout), "0000");
+
+    fs::remove_file(&book).ok();
+}
+
+#[test]
+fn reports_unusable_book_files() {
+    let (_, stderr) = uci("setoption name BookFile value /nonexistent/book.bin");
+    assert!(stderr.contains("Can't load book"), "{}", stderr);
+
+    let path = env::temp_dir().join(format!("chess-engine-short-{}.bin", std::process::id()));
+    fs::write(&path, [0u8; 20]).unwrap();
+    let (_, stderr) = uci(&format!("setoption name BookFile value {}", path.display()));
+    assert!(stderr.contains("not a whole number"), "{}", stderr);
+
+    fs::remove_file(&path).ok();
+}
@@ -0,0 +1,159 @@
+// Opening books: play through the OwnBook and BookFile UCI options, and the `book build`
+// subcommand.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+// Polyglot key of the start position and the move g2g4 in Polyglot's encoding
+const START_KEY: u64 = 0x463B96181691FC9C;
+const G2G4: u16 = 14 << 6 | 30;
+
+fn write_book(name: &str, entries: &[(u64, u16, u16)]) -> PathBuf {
+    let mut bytes = Vec::new();
+    for &(key, mov, weight) in entries {
+        bytes.extend(key.to_be_bytes());
+        bytes.extend(mov.to_be_bytes());
+        bytes.extend(weight.to_be_bytes());
+        bytes.extend(0u32.to_be_bytes());
+    }
+
+    let path = env::temp_dir().join(format!("chess-engine-{}-{}.bin", name, std::process::id()));
+    fs::write(&path, bytes).unwrap();
+    path
+}
+
+// Everything the engine prints for a script of UCI commands
+fn uci(commands: &str) -> (String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run chess-engine");
+
+    child.stdin.take().unwrap().write_all(format!("{}\nquit\n", commands).as_bytes()).unwrap();
+
+    let output = child.wait_with_output().expect("chess-engine did not finish");
+    assert!(output.status.success(), "chess-engine exited with {}", output.status);
+    (String::from_utf8_lossy(&output.stdout).to_string(), String::from_utf8_lossy(&output.stderr).to_string())
+}
+
+fn bestmove(stdout: &str) -> &str {
+    stdout.lines().find_map(|line| line.strip_prefix("bestmove ")).expect("no bestmove")
+}
+
+#[test]
+fn plays_book_moves_when_enabled() {
+    let book = write_book("own-book", &[(START_KEY, G2G4, 1)]);
+    let load = format!("setoption name BookFile value {}", book.display());
+
+    let enabled = format!("{}\nsetoption name OwnBook value true", load);
+    let (stdout, _) = uci(&format!("{}\nposition startpos\ngo depth 4", enabled));
+    assert_eq!(bestmove(&stdout), "g2g4");
+
+    // Off by default, and out of book the engine searches as usual
+    let (stdout, _) = uci(&format!("{}\nposition startpos\ngo depth 4", load));
+    assert_ne!(bestmove(&stdout), "g2g4");
+    let (stdout, _) = uci(&format!("{}\nposition startpos moves e2e4\ngo depth 4", enabled));
+    assert_ne!(bestmove(&stdout), "0000");
+
+    fs::remove_file(&book).ok();
+}
+
+#[test]
+fn reports_unusable_book_files() {
+    let (_, stderr) = uci("setoption name BookFile value /nonexistent/book.bin");
+    assert!(stderr.contains("Can't load book"), "{}", stderr);
+
+    let path = env::temp_dir().join(format!("chess-engine-short-{}.bin", std::process::id()));
+    fs::write(&path, [0u8; 20]).unwrap();
+    let (_, stderr) = uci(&format!("setoption name BookFile value {}", path.display()));
+    assert!(stderr.contains("not a whole number"), "{}", stderr);
+
+    fs::remove_file(&path).ok();
+}
+
+// Four short games: 1.e4 scores 1/2 over two games, 1.d4 1/2 over one, and against 1.e4
+// black won with 1...c5 but lost with 1...e5. The unfinished game doesn't count.
+const GAMES: &str = "\
+[Result \"1-0\"]
+
+1. e4 e5 2. Nf3 Nc6 1-0
+
+[Result \"0-1\"]
+
+1. e4 c5 2. Nf3 d6 0-1
+
+[Result \"1/2-1/2\"]
+
+1. d4 d5 1/2-1/2
+
+[Result \"*\"]
+
+1. e4 e5 2. Bc4 *
+";
+
+fn build(name: &str, flags: &[&str]) -> (PathBuf, String) {
+    let pgn = env::temp_dir().join(format!("chess-engine-{}-{}.pgn", name, std::process::id()));
+    let book = env::temp_dir().join(format!("chess-engine-{}-{}.bin", name, std::process::id()));
+    fs::write(&pgn, GAMES).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .args(["book", "build", pgn.to_str().unwrap(), book.to_str().unwrap()])
+        .args(flags)
+        .output()
+        .expect("failed to run chess-engine");
+    fs::remove_file(&pgn).ok();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    (book, String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// The move the engine takes from a book, choosing by weight
+fn book_move(book: &Path, position: &str) -> String {
+    let commands = format!(
+        "setoption name BookFile value {}\nsetoption name OwnBook value true\n\
+         setoption name Best Book Move value true\nposition {}\ngo depth 1",
+        book.display(),
+        position
+    );
+    let (stdout, _) = uci(&commands);
+    bestmove(&stdout).to_string()
+}
+
+#[test]
+fn builds_a_book_from_pgn() {
+    let (book, report) = build("built", &["--max-ply", "2", "--min-games", "1"]);
+    assert!(report.contains("Games: 3 (1 skipped)"), "{}", report);
+    assert!(report.contains("Positions: 3"), "{}", report);
+    assert!(report.contains("Entries: 5"), "{}", report);
+    assert_eq!(fs::metadata(&book).unwrap().len(), 5 * 16);
+
+    // The start position is first looked up under its Polyglot key
+    let bytes = fs::read(&book).unwrap();
+    assert!(bytes.chunks(16).any(|entry| entry[..8] == START_KEY.to_be_bytes()));
+
+    assert_eq!(book_move(&book, "startpos"), "e2e4");
+    assert_eq!(book_move(&book, "startpos moves e2e4"), "c7c5"); // 1...e5 lost, so has weight 0
+    assert_eq!(book_move(&book, "startpos moves d2d4"), "d7d5");
+
+    fs::remove_file(&book).ok();
+}
+
+#[test]
+fn filters_by_games_and_score() {
+    // Only 1...c5 scored better than even
+    let (book, report) = build("score", &["--max-ply", "2", "--min-games", "1", "--min-score", "60"]);
+    assert!(report.contains("Entries: 1"), "{}", report);
+    assert_eq!(book_move(&book, "startpos moves e2e4"), "c7c5");
+    fs::remove_file(&book).ok();
+
+    // 1.e4 is the only move played twice
+    let (book, report) = build("games", &["--max-ply", "2", "--min-games", "2"]);
+    assert!(report.contains("Positions: 1") && report.contains("Entries: 1"), "{}", report);
+    assert_eq!(book_move(&book, "startpos"), "e2e4");
+    fs::remove_file(&book).ok();
+}
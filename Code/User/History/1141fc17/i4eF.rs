@@ -0,0 +1,92 @@
+use crate::movegen::bishops::BISHOP_MAGIC;
+use crate::movegen::kings::KING_ATTACKS;
+use crate::movegen::knights;
+use crate::movegen::pawns::{Bitboard, BLACK_ATTACKING, WHITE_ATTACKING};
+use crate::movegen::queens::BISHOP_MAGICS_TABLE;
+use crate::movegen::rooks::ROOK_MAGICS_TABLE;
+use crate::state::board::{Board, Color, Piece};
+
+/// Every piece (of either colour) attacking `sq`, with sliders seeing through
+/// nothing but the given occupancy
+pub fn attackers_to(board: &Board, sq: u8, occupancy: Bitboard) -> Bitboard {
+    let sq = sq as usize;
+    let white = &board.bitboards[Color::White as usize];
+    let black = &board.bitboards[Color::Black as usize];
+
+    let knights = white[Piece::Knight as usize] | black[Piece::Knight as usize];
+    let kings = white[Piece::King as usize] | black[Piece::King as usize];
+    let rooks_queens = white[Piece::Rook as usize]
+        | black[Piece::Rook as usize]
+        | white[Piece::Queen as usize]
+        | black[Piece::Queen as usize];
+    let bishops_queens = white[Piece::Bishop as usize]
+        | black[Piece::Bishop as usize]
+        | white[Piece::Queen as usize]
+        | black[Piece::Queen as usize];
+
+    // A white pawn attacks sq exactly when a black pawn on sq would attack it, and vice versa
+    (BLACK_ATTACKING[sq] & white[Piece::Pawn as usize])
+        | (WHITE_ATTACKING[sq] & black[Piece::Pawn as usize])
+        | (knights::ATTACKING[sq] & knights)
+        | (KING_ATTACKS[sq] & kings)
+        | (ROOK_MAGICS_TABLE[sq].attacks(occupancy) & rooks_queens)
+        | (BISHOP_MAGIC.bishop_attacks(sq, occupancy) & bishops_queens)
+}
+
+/// Is `sq` attacked by any piece of colour `by`?
+pub fn is_square_attacked(board: &Board, sq: u8, by: Color) -> bool {
+    attackers_to(board, sq, board.occupancies[2]) & board.occupancies[by as usize] != 0
+}
+
+/// Enemy pieces giving check to the given side's king
+pub fn checkers(board: &Board, color: Color) -> Bitboard {
+    let king_bb = board.bitboards[color as usize][Piece::King as usize];
+    if king_bb == 0 {
+        return 0;
+    }
+
+    let king_sq = king_bb.trailing_zeros() as u8;
+    attackers_to(board, king_sq, board.occupancies[2]) & board.occupancies[color.opposite() as usize]
+}
+
+lazy_static::lazy_static! {
+    // Squares strictly between two squares sharing a rank, file or diagonal (0 otherwise)
+    pub static ref BETWEEN: Vec<[Bitboard; 64]> = build_line_tables(false);
+
+    // The whole rank, file or diagonal through two aligned squares (0 otherwise)
+    pub static ref LINE: Vec<[Bitboard; 64]> = build_line_tables(true);
+}
+
+fn build_line_tables(full_line: bool) -> Vec<[Bitboard; 64]> {
+    let mut table = vec![[0; 64]; 64];
+
+    for a in 0..64 {
+        for b in 0..64 {
+            if a == b {
+                continue;
+            }
+
+            let (a_bb, b_bb) = (1u64 << a, 1u64 << b);
+            let rook_a = ROOK_MAGICS_TABLE[a].attacks(0);
+            let bishop_a = BISHOP_MAGICS_TABLE[a].get_attacks(0);
+
+            table[a][b] = if rook_a & b_bb != 0 {
+                if full_line {
+                    (rook_a & ROOK_MAGICS_TABLE[b].attacks(0)) | a_bb | b_bb
+                } else {
+                    ROOK_MAGICS_TABLE[a].attacks(b_bb) & ROOK_MAGICS_TABLE[b].attacks(a_bb)
+                }
+            } else if bishop_a & b_bb != 0 {
+                if full_line {
+                    (bishop_a & BISHOP_MAGICS_TABLE[b].get_attacks(0)) | a_bb | b_bb
+                } else {
+                    BISHOP_MAGICS_TABLE[a].get_attacks(b_bb) & BISHOP_MAGICS_TABLE[b].get_attacks(a_bb)
+                }
+            } else {
+                0
+            };
+        }
+    }
+
+    table
+}
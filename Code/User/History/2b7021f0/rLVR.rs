@@ -0,0 +1,351 @@
+pub mod builder;
+mod random;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::movegen::generate::generate_legal_moves;
+use crate::state::board::{Board, Color, Piece, Square};
+use crate::state::r#move::{from_square, is_castling, promo_piece, to_square, Move};
+
+use self::random::RANDOM64;
+
+pub use self::builder::{build, BuildOptions};
+
+// Offsets into RANDOM64 after the 768 piece keys
+const CASTLING_OFFSET: usize = 768;
+const EN_PASSANT_OFFSET: usize = 772;
+const TURN_OFFSET: usize = 780;
+
+// key (8 bytes), move (2), weight (2), learn (4), all big-endian
+const ENTRY_SIZE: usize = 16;
+
+/// Position key as Polyglot computes it, which is what book entries are looked up by
+///
+/// The en passant file only counts when a pawn of the side to move could actually capture
+/// there, so it can differ from `Board::hash` in more than just the random numbers used.
+pub fn polyglot_key(board: &Board) -> u64 {
+    let mut key = 0;
+
+    for sq in 0..64 {
+        if let Some((piece, color)) = board.pieces[sq] {
+            // Kinds run black pawn, white pawn, black knight, ... white king
+            let kind = 2 * piece as usize + (color == Color::White) as usize;
+            key ^= RANDOM64[64 * kind + sq];
+        }
+    }
+
+    let rights = [
+        board.castling.white_kingside,
+        board.castling.white_queenside,
+        board.castling.black_kingside,
+        board.castling.black_queenside,
+    ];
+    for (i, &right) in rights.iter().enumerate() {
+        if right {
+            key ^= RANDOM64[CASTLING_OFFSET + i];
+        }
+    }
+
+    if let Some(ep) = board.en_passant
+        && board.en_passant_capturable(ep, board.side_to_move)
+    {
+        key ^= RANDOM64[EN_PASSANT_OFFSET + (ep.0 % 8) as usize];
+    }
+
+    if board.side_to_move == Color::White {
+        key ^= RANDOM64[TURN_OFFSET];
+    }
+
+    key
+}
+
+/// How to choose among the book moves of a position
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Selection {
+    BestWeight,
+    Weighted(u64), // random number that picks a move with probability in proportion to its weight
+}
+
+impl Selection {
+    /// Weighted selection seeded from the clock, so games don't all follow the same line
+    pub fn random() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+
+        // Spread the low-entropy clock bits over the whole word (SplitMix64 finaliser)
+        let mut z = nanos.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Selection::Weighted(z ^ (z >> 31))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct BookEntry {
+    key: u64,
+    mov: u16,
+    weight: u16,
+}
+
+impl BookEntry {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            key: u64::from_be_bytes(bytes[0..8].try_into().unwrap()),
+            mov: u16::from_be_bytes(bytes[8..10].try_into().unwrap()),
+            weight: u16::from_be_bytes(bytes[10..12].try_into().unwrap()),
+        }
+    }
+
+    // The learn field is left at zero; nothing reads it back
+    fn to_bytes(self) -> [u8; ENTRY_SIZE] {
+        let mut bytes = [0; ENTRY_SIZE];
+        bytes[0..8].copy_from_slice(&self.key.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.mov.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.weight.to_be_bytes());
+        bytes
+    }
+}
+
+/// An opening book in Polyglot `.bin` format, held in memory
+///
+/// Entries are sorted by key, so the moves of a position are found with a binary search.
+pub struct Book {
+    entries: Vec<BookEntry>,
+}
+
+impl Book {
+    pub fn open(path: &str) -> Result<Self, BookError> {
+        let bytes = fs::read(path)?;
+        if !bytes.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(BookError::BadSize(bytes.len()));
+        }
+
+        let entries = bytes.chunks_exact(ENTRY_SIZE).map(BookEntry::from_bytes).collect();
+
+        Ok(Self { entries })
+    }
+
+    /// The book moves of a position with their weights, in book order
+    ///
+    /// Entries that don't decode to a legal move (hash collisions, broken books) are dropped.
+    pub fn moves(&self, board: &Board) -> Vec<(Move, u16)> {
+        let key = polyglot_key(board);
+        let first = self.entries.partition_point(|entry| entry.key < key);
+        let legal = generate_legal_moves(board);
+
+        self.entries[first..]
+            .iter()
+            .take_while(|entry| entry.key == key)
+            .filter_map(|entry| {
+                let (from, to, promo) = decode_move(board, entry.mov);
+                let m = legal.iter().copied().find(|&m| {
+                    from_square(m) == from && to_square(m) == to && promo_piece(m) == promo
+                })?;
+                Some((m, entry.weight))
+            })
+            .collect()
+    }
+
+    /// A book move for the position, or None once the game has left the book
+    ///
+    /// Moves with weight 0 are in the book only to be avoided and are never chosen.
+    pub fn pick(&self, board: &Board, selection: Selection) -> Option<Move> {
+        let moves: Vec<(Move, u16)> = self.moves(board).into_iter().filter(|&(_, weight)| weight > 0).collect();
+
+        match selection {
+            Selection::BestWeight => {
+                // The first of equally weighted moves, as Polyglot does
+                let best = moves.iter().map(|&(_, weight)| weight).max()?;
+                moves.iter().find(|&&(_, weight)| weight == best).map(|&(m, _)| m)
+            }
+            Selection::Weighted(random) => {
+                let total: u64 = moves.iter().map(|&(_, weight)| weight as u64).sum();
+                if total == 0 {
+                    return None;
+                }
+
+                let mut target = random % total;
+                for &(m, weight) in &moves {
+                    if target < weight as u64 {
+                        return Some(m);
+                    }
+                    target -= weight as u64;
+                }
+                None
+            }
+        }
+    }
+}
+
+// From square, to square and promotion piece of a Polyglot move
+//
+// Polyglot writes castling as the king taking its own rook (e1h1); the king's actual
+// destination is what our castling moves use.
+fn decode_move(board: &Board, mov: u16) -> (u8, u8, u32) {
+    let to = (mov & 0x3F) as u8;
+    let from = ((mov >> 6) & 0x3F) as u8;
+    let promo = ((mov >> 12) & 0x7) as u32; // 1 knight .. 4 queen, the same as PROMO_N .. PROMO_Q
+
+    // A king can't otherwise get from its start square to a corner in one move
+    let king_start = matches!(from, 4 | 60) && board.piece_at(Square(from)).is_some_and(|(p, _)| p == Piece::King);
+    if king_start && to == from + 3 {
+        (from, from + 2, promo)
+    } else if king_start && to + 4 == from {
+        (from, from - 2, promo)
+    } else {
+        (from, to, promo)
+    }
+}
+
+// Our move in Polyglot's encoding, the reverse of `decode_move`
+fn encode_move(m: Move) -> u16 {
+    let from = from_square(m) as u16;
+    let mut to = to_square(m) as u16;
+
+    // The king "takes" the rook it castles with
+    if is_castling(m) {
+        to = if to > from { from + 3 } else { from - 4 };
+    }
+
+    (promo_piece(m) as u16) << 12 | from << 6 | to
+}
+
+/// Reasons a book file can't be loaded or written
+#[derive(Debug)]
+pub enum BookError {
+    Io(io::Error),
+    BadSize(usize), // file length that isn't a whole number of entries
+}
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BookError::Io(e) => write!(f, "{}", e),
+            BookError::BadSize(len) => write!(f, "{} bytes is not a whole number of {}-byte entries", len, ENTRY_SIZE),
+        }
+    }
+}
+
+impl std::error::Error for BookError {}
+
+impl From<io::Error> for BookError {
+    fn from(e: io::Error) -> Self {
+        BookError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::board::START_FEN;
+    use crate::state::make_move::make_move;
+    use crate::state::state::GameState;
+    use crate::uci::parse_move;
+
+    fn board(fen: &str) -> Board {
+        Board::from_fen(fen).expect("valid FEN")
+    }
+
+    fn book(entries: &[(u64, u16, u16)]) -> Book {
+        let mut entries: Vec<BookEntry> =
+            entries.iter().map(|&(key, mov, weight)| BookEntry { key, mov, weight }).collect();
+        entries.sort_by_key(|entry| entry.key);
+        Book { entries }
+    }
+
+    fn play(board: &mut Board, text: &str) {
+        let m = parse_move(board, text).expect("legal move");
+        make_move(board, m, &mut GameState::new());
+    }
+
+    // Polyglot move for coordinates given as (file, rank) pairs
+    fn polyglot_move(from: (u16, u16), to: (u16, u16)) -> u16 {
+        (from.1 * 8 + from.0) << 6 | (to.1 * 8 + to.0)
+    }
+
+    #[test]
+    fn reference_keys() {
+        // The examples from the Polyglot book format description, move by move from the start
+        let expected: [(&str, u64); 7] = [
+            ("", 0x463B96181691FC9C),
+            ("e2e4", 0x823C9B50FD114196),
+            ("d7d5", 0x0756B94461C50FB0),
+            ("e4e5", 0x662FAFB965DB29D4),
+            ("f7f5", 0x22A48B5A8E47FF78),
+            ("e1e2", 0x652A607CA3F242C1),
+            ("e8f7", 0x00FDD303C946BDD9),
+        ];
+
+        let mut position = board(START_FEN);
+        for (text, key) in expected {
+            if !text.is_empty() {
+                play(&mut position, text);
+            }
+            assert_eq!(polyglot_key(&position), key, "after {}", text);
+        }
+
+        // Same again for a line with en passant possible, then castling rights lost
+        let mut position = board(START_FEN);
+        for text in ["a2a4", "b7b5", "h2h4", "b5b4", "c2c4"] {
+            play(&mut position, text);
+        }
+        assert_eq!(polyglot_key(&position), 0x3C8123EA7B067637);
+
+        for text in ["b4c3", "a1a3"] {
+            play(&mut position, text);
+        }
+        assert_eq!(polyglot_key(&position), 0x5C3F9B829B279560);
+    }
+
+    #[test]
+    fn selection() {
+        let start = board(START_FEN);
+        let key = polyglot_key(&start);
+        let e4 = polyglot_move((4, 1), (4, 3));
+        let d4 = polyglot_move((3, 1), (3, 3));
+        let a3 = polyglot_move((0, 1), (0, 2));
+        let book = book(&[(key, e4, 30), (key, d4, 70), (key, a3, 0), (key ^ 1, a3, 100)]);
+
+        let uci = |m: Option<Move>| m.map(crate::state::r#move::move_to_uci);
+        assert_eq!(book.moves(&start).len(), 3);
+        assert_eq!(uci(book.pick(&start, Selection::BestWeight)), Some("d2d4".to_string()));
+        assert_eq!(uci(book.pick(&start, Selection::Weighted(0))), Some("e2e4".to_string()));
+        assert_eq!(uci(book.pick(&start, Selection::Weighted(29))), Some("e2e4".to_string()));
+        assert_eq!(uci(book.pick(&start, Selection::Weighted(30))), Some("d2d4".to_string()));
+        assert_eq!(uci(book.pick(&start, Selection::Weighted(100))), Some("e2e4".to_string()));
+
+        // Out of book
+        let after = board("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        assert_eq!(book.pick(&after, Selection::BestWeight), None);
+    }
+
+    #[test]
+    fn castling_moves() {
+        let fen = "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1";
+        let white = board(fen);
+        let black = board(&fen.replace(" w ", " b "));
+
+        let book = book(&[
+            (polyglot_key(&white), polyglot_move((4, 0), (7, 0)), 1),
+            (polyglot_key(&white), polyglot_move((4, 0), (0, 0)), 1),
+            (polyglot_key(&black), polyglot_move((4, 7), (0, 7)), 1),
+        ]);
+
+        let white_moves = book.moves(&white);
+        assert_eq!(white_moves.len(), 2);
+        assert!(white_moves.iter().all(|&(m, _)| is_castling(m)));
+        assert_eq!(to_square(white_moves[0].0), 6); // g1
+        assert_eq!(to_square(white_moves[1].0), 2); // c1
+
+        // and back to the king taking the rook
+        assert_eq!(encode_move(white_moves[0].0), polyglot_move((4, 0), (7, 0)));
+        assert_eq!(encode_move(white_moves[1].0), polyglot_move((4, 0), (0, 0)));
+
+        let black_moves = book.moves(&black);
+        assert_eq!(black_moves.len(), 1);
+        assert!(is_castling(black_moves[0].0));
+        assert_eq!(to_square(black_moves[0].0), 58); // c8
+    }
+}
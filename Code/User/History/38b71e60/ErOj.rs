@@ -0,0 +1,8 @@
+pub mod pawns;
+pub mod knights;
+pub mod bishops;
+pub mod rooks;
+pub mod queens;
+pub mod kings;
+pub mod attacks;
+pub mod generate;
\ No newline at end of file
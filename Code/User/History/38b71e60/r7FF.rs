@@ -0,0 +1,22 @@
+pub mod pawns;
+pub mod knights;
+pub mod bishops;
+pub mod rooks;
+pub mod queens;
+pub mod kings;
+pub mod attacks;
+pub mod generate;
+
+// Every lookup table in here is filled in once (at compile time, or by `lazy_static` on first
+// use) and only ever read afterwards, so the search threads can all share them without locks.
+// `lazy_static` already refuses a table type that isn't `Sync`; this spells it out for the
+// tables the multi-threaded search reads, so it can't quietly stop being true.
+const _: () = {
+    const fn assert_sync<T: Sync>() {}
+
+    assert_sync::<rooks::RookMagic>();
+    assert_sync::<bishops::BishopMagic>();
+    assert_sync::<queens::Magic>();
+    assert_sync::<Vec<[pawns::Bitboard; 64]>>();
+    assert_sync::<crate::state::zobrist::Zobrist>();
+};
@@ -0,0 +1,54 @@
+use crate::movegen::pawns::Bitboard;
+use crate::state::board::{Board, CastlingRights, Color, Piece, Square};
+use crate::state::r#move::Move;
+use crate::state::zobrist::ZOBRIST;
+
+/// Everything make_move destroys that undo_move needs back
+#[derive(Copy, Clone)]
+pub struct GameState {
+    pub captured_piece: Option<(Piece, Color)>,
+    pub castling_rights: CastlingRights,
+    pub en_passant: Option<Square>,
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+    pub checkers: Bitboard,
+}
+
+impl GameState {
+    pub fn new() -> Self {
+        Self {
+            captured_piece: None,
+            castling_rights: CastlingRights {
+                white_kingside: false,
+                white_queenside: false,
+                black_kingside: false,
+                black_queenside: false,
+            },
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            checkers: 0,
+        }
+    }
+
+    pub fn save(&mut self, board: &Board, _mov: Move) {
+        self.captured_piece = None;
+        self.castling_rights = board.castling;
+        self.en_passant = board.en_passant;
+        self.halfmove_clock = board.halfmove_clock;
+        self.fullmove_number = board.fullmove_number;
+        self.checkers = board.checkers;
+    }
+
+    pub fn restore_state(&self, board: &mut Board) {
+        // Swap the castling and en passant keys back along with the state itself
+        board.hash ^= ZOBRIST.castling(board.castling) ^ ZOBRIST.en_passant(board.en_passant);
+        board.hash ^= ZOBRIST.castling(self.castling_rights) ^ ZOBRIST.en_passant(self.en_passant);
+
+        board.castling = self.castling_rights;
+        board.en_passant = self.en_passant;
+        board.halfmove_clock = self.halfmove_clock;
+        board.fullmove_number = self.fullmove_number;
+        board.checkers = self.checkers;
+    }
+}
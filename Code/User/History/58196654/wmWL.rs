@@ -0,0 +1,222 @@
+use crate::eval::{pawn_attacks, Score};
+use crate::movegen::pawns::{
+    Bitboard, A_FILE, H_FILE, RANK_1, RANK_2, RANK_3, RANK_4, RANK_5, RANK_6, RANK_7, RANK_8,
+};
+use crate::state::board::{Board, Color, Piece};
+
+const RANKS: [Bitboard; 8] = [RANK_1, RANK_2, RANK_3, RANK_4, RANK_5, RANK_6, RANK_7, RANK_8];
+
+// Indexed by the pawn's rank counted from its own side (1 = starting rank, 6 = seventh rank)
+const PASSED: [Score; 8] = [
+    Score::new(0, 0),
+    Score::new(5, 10),
+    Score::new(10, 20),
+    Score::new(15, 35),
+    Score::new(30, 60),
+    Score::new(55, 100),
+    Score::new(90, 150),
+    Score::new(0, 0),
+];
+const CONNECTED: [i32; 8] = [0, 5, 7, 10, 15, 25, 40, 0];
+
+const ISOLATED: Score = Score::new(-10, -15);
+const DOUBLED: Score = Score::new(-10, -25);
+const BACKWARD: Score = Score::new(-8, -10);
+
+// Per pawn directly in front of a castled king, and one rank further up
+const SHIELD_NEAR: i32 = 12;
+const SHIELD_FAR: i32 = 6;
+
+// Entries in the pawn hash table (a power of two)
+const PAWN_TABLE_SIZE: usize = 1 << 14;
+
+#[derive(Clone, Copy, Default)]
+struct PawnEntry {
+    key: u64,
+    score: Score,
+}
+
+/// Cache of pawn-structure scores keyed by `Board::pawn_hash`
+///
+/// Pawn skeletons change far less often than the rest of the position, so most probes hit.
+/// Each search thread owns its own table.
+pub struct PawnTable {
+    entries: Vec<PawnEntry>,
+}
+
+impl PawnTable {
+    pub fn new() -> Self {
+        Self {
+            entries: vec![PawnEntry::default(); PAWN_TABLE_SIZE],
+        }
+    }
+
+    /// White-relative pawn-structure score, computed on a miss
+    pub fn probe(&mut self, board: &Board) -> Score {
+        let entry = &mut self.entries[board.pawn_hash as usize & (PAWN_TABLE_SIZE - 1)];
+
+        // An empty entry has key 0, which only collides with the pawnless position (worth 0)
+        if entry.key != board.pawn_hash {
+            entry.key = board.pawn_hash;
+            entry.score = structure(board, Color::White) - structure(board, Color::Black);
+        }
+
+        entry.score
+    }
+}
+
+// Spread every bit to the top / bottom of its file
+fn north_fill(mut bb: Bitboard) -> Bitboard {
+    bb |= bb << 8;
+    bb |= bb << 16;
+    bb |= bb << 32;
+    bb
+}
+
+fn south_fill(mut bb: Bitboard) -> Bitboard {
+    bb |= bb >> 8;
+    bb |= bb >> 16;
+    bb |= bb >> 32;
+    bb
+}
+
+fn file_fill(bb: Bitboard) -> Bitboard {
+    north_fill(bb) | south_fill(bb)
+}
+
+// The same squares shifted one file sideways
+fn neighbours(bb: Bitboard) -> Bitboard {
+    ((bb & !A_FILE) >> 1) | ((bb & !H_FILE) << 1)
+}
+
+/// Squares in front of `bb` on the same file, from `color`'s point of view
+pub fn front_span(bb: Bitboard, color: Color) -> Bitboard {
+    match color {
+        Color::White => north_fill(bb << 8),
+        Color::Black => south_fill(bb >> 8),
+    }
+}
+
+fn rear_span(bb: Bitboard, color: Color) -> Bitboard {
+    front_span(bb, color.opposite())
+}
+
+fn relative_rank(sq: u8, color: Color) -> usize {
+    match color {
+        Color::White => (sq / 8) as usize,
+        Color::Black => 7 - (sq / 8) as usize,
+    }
+}
+
+/// Pawns of `color` that no enemy pawn can stop or capture on the way to promotion
+pub fn passed_pawns(board: &Board, color: Color) -> Bitboard {
+    let ours = board.bitboards[color as usize][Piece::Pawn as usize];
+    let theirs = board.bitboards[color.opposite() as usize][Piece::Pawn as usize];
+
+    // Everything enemy pawns block or guard on the way down the board
+    let front = front_span(theirs, color.opposite());
+    let stoppers = front | neighbours(front) | theirs;
+
+    ours & !stoppers & !rear_span(ours, color)
+}
+
+// Pawn-structure terms for one side
+fn structure(board: &Board, color: Color) -> Score {
+    let them = color.opposite();
+    let ours = board.bitboards[color as usize][Piece::Pawn as usize];
+    let theirs = board.bitboards[them as usize][Piece::Pawn as usize];
+
+    let our_attacks = pawn_attacks(ours, color);
+    let their_attacks = pawn_attacks(theirs, them);
+
+    // Squares our pawns could ever attack by advancing
+    let attack_span = front_span(our_attacks, color) | our_attacks;
+
+    let passed = passed_pawns(board, color);
+    let isolated = ours & !file_fill(neighbours(ours));
+    let doubled = ours & front_span(ours, color);
+    let supported = ours & (our_attacks | neighbours(ours));
+
+    let mut score = Score::default();
+    let mut bb = ours;
+
+    while bb != 0 {
+        let sq = bb.trailing_zeros() as u8;
+        let pawn = 1u64 << sq;
+        let rank = relative_rank(sq, color);
+
+        if passed & pawn != 0 {
+            score += PASSED[rank];
+        } else if is_candidate(pawn, color, ours, theirs) {
+            score += Score::new(PASSED[rank].mg / 2, PASSED[rank].eg / 2);
+        }
+
+        if isolated & pawn != 0 {
+            score += ISOLATED;
+        } else {
+            // Backward: the square ahead is guarded by an enemy pawn and no friendly pawn
+            // can ever come alongside to cover it
+            let stop = match color {
+                Color::White => pawn << 8,
+                Color::Black => pawn >> 8,
+            };
+            if stop & their_attacks != 0 && stop & attack_span == 0 {
+                score += BACKWARD;
+            }
+        }
+
+        if doubled & pawn != 0 {
+            score += DOUBLED;
+        }
+
+        if supported & pawn != 0 {
+            score += Score::new(CONNECTED[rank], CONNECTED[rank]);
+        }
+
+        bb &= bb - 1;
+    }
+
+    score
+}
+
+// A pawn on a half-open file whose advance can be forced: at least as many friendly pawns
+// able to support it as enemy pawns guarding the squares ahead
+fn is_candidate(pawn: Bitboard, color: Color, ours: Bitboard, theirs: Bitboard) -> bool {
+    let front = front_span(pawn, color);
+    if front & theirs != 0 {
+        return false;
+    }
+
+    let sentries = neighbours(front) & theirs;
+    let helpers = neighbours(rear_span(pawn, color) | pawn) & ours;
+
+    helpers.count_ones() >= sentries.count_ones()
+}
+
+/// Bonus for pawns in front of a king still sitting on its back two ranks
+///
+/// Depends on the king square, so it is scored outside the pawn hash table.
+pub fn pawn_shield(board: &Board, color: Color) -> Score {
+    let king = board.bitboards[color as usize][Piece::King as usize];
+    let ours = board.bitboards[color as usize][Piece::Pawn as usize];
+
+    let home = match color {
+        Color::White => RANK_1 | RANK_2,
+        Color::Black => RANK_7 | RANK_8,
+    };
+    if king & home == 0 {
+        return Score::default();
+    }
+
+    let files = file_fill(king | neighbours(king));
+    let king_rank = king.trailing_zeros() as usize / 8;
+    let (near_rank, far_rank) = match color {
+        Color::White => (RANKS[king_rank + 1], RANKS[(king_rank + 2).min(7)]),
+        Color::Black => (RANKS[king_rank - 1], RANKS[king_rank.saturating_sub(2)]),
+    };
+
+    let shield = SHIELD_NEAR * (ours & files & near_rank).count_ones() as i32
+        + SHIELD_FAR * (ours & files & far_rank).count_ones() as i32;
+
+    Score::new(shield, 0)
+}
@@ -0,0 +1,550 @@
+use std::fmt;
+
+use crate::eval::{pst, Score};
+use crate::movegen::attacks::checkers;
+use crate::movegen::pawns::Bitboard;
+use crate::state::r#move::square_to_coord;
+use crate::state::state::GameState;
+use crate::state::zobrist::{compute_hash, compute_pawn_hash, ZOBRIST};
+
+/// Standard starting position
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    pub fn opposite(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Piece {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Square(pub u8); // 0..63
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CastlingRights {
+    pub white_kingside: bool,
+    pub white_queenside: bool,
+    pub black_kingside: bool,
+    pub black_queenside: bool,
+}
+
+impl CastlingRights {
+    /// Rights packed as bits (K = 1, Q = 2, k = 4, q = 8)
+    pub fn index(&self) -> usize {
+        (self.white_kingside as usize)
+            | (self.white_queenside as usize) << 1
+            | (self.black_kingside as usize) << 2
+            | (self.black_queenside as usize) << 3
+    }
+}
+
+/// Reasons a FEN string can be rejected by `Board::load_fen`
+#[derive(Clone, PartialEq, Debug)]
+pub enum FenError {
+    MissingField(&'static str),
+    TooManyFields(usize),
+    BadRankCount(usize),
+    BadRankLength(u8),             // rank number (1..8) that did not add up to 8 files
+    InvalidPiece(char),
+    BadKingCount(Color, u32),      // side without exactly one king, and how many it has
+    InvalidSideToMove(String),
+    InvalidCastling(String),
+    InvalidEnPassant(String),
+    InvalidClock(String),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FenError::MissingField(field) => write!(f, "missing {} field", field),
+            FenError::TooManyFields(n) => write!(f, "expected at most 6 fields, found {}", n),
+            FenError::BadRankCount(n) => write!(f, "expected 8 ranks, found {}", n),
+            FenError::BadRankLength(rank) => write!(f, "rank {} does not describe 8 files", rank),
+            FenError::InvalidPiece(c) => write!(f, "invalid piece character '{}'", c),
+            FenError::BadKingCount(color, n) => {
+                let side = if *color == Color::White { "white" } else { "black" };
+                write!(f, "expected one {} king, found {}", side, n)
+            }
+            FenError::InvalidSideToMove(s) => write!(f, "invalid side to move '{}'", s),
+            FenError::InvalidCastling(s) => write!(f, "invalid castling rights '{}'", s),
+            FenError::InvalidEnPassant(s) => write!(f, "invalid en passant square '{}'", s),
+            FenError::InvalidClock(s) => write!(f, "invalid move clock '{}'", s),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+#[derive(Clone)]
+pub struct Board {
+    pub pieces: [Option<(Piece, Color)>; 64], // Square-wise representation
+    pub bitboards: [[Bitboard; 6]; 2],        // [color][piece_type]
+    pub occupancies: [Bitboard; 3],           // [white, black, all]
+
+    pub side_to_move: Color,
+    pub castling: CastlingRights,
+    pub en_passant: Option<Square>,           // only set when a pawn can actually take there
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+
+    pub checkers: Bitboard,                   // enemy pieces checking the side to move
+
+    pub hash: u64,                            // Zobrist key of the full position
+    pub pawn_hash: u64,                       // Zobrist key of the pawns only
+
+    pub psqt: [Score; 2],                     // running material + piece-square totals per color
+
+    pub history: Vec<u64>,                    // keys of the positions that led here, oldest first
+}
+
+impl Board {
+    pub fn new() -> Self {
+        // Create an empty board (you can override with FEN later)
+        Self {
+            pieces: [None; 64],
+            bitboards: [[0; 6]; 2],
+            occupancies: [0; 3],
+            side_to_move: Color::White,
+            castling: CastlingRights {
+                white_kingside: false,
+                white_queenside: false,
+                black_kingside: false,
+                black_queenside: false,
+            },
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            checkers: 0,
+            hash: 0,
+            pawn_hash: 0,
+            psqt: [Score::default(); 2],
+            history: Vec::new(),
+        }
+    }
+
+    /// Build a board straight from a FEN string
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let mut board = Board::new();
+        board.load_fen(fen)?;
+        Ok(board)
+    }
+
+    /// Load a FEN string into the board
+    /// The halfmove and fullmove clocks may be omitted (defaulting to 0 and 1).
+    /// On error the board is left untouched.
+    pub fn load_fen(&mut self, fen: &str) -> Result<(), FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() > 6 {
+            return Err(FenError::TooManyFields(fields.len()));
+        }
+
+        let placement = *fields.first().ok_or(FenError::MissingField("piece placement"))?;
+        let side = *fields.get(1).ok_or(FenError::MissingField("side to move"))?;
+        let castling = *fields.get(2).ok_or(FenError::MissingField("castling"))?;
+        let en_passant = *fields.get(3).ok_or(FenError::MissingField("en passant"))?;
+
+        // Build into a scratch board so a bad FEN never leaves us half-loaded
+        let mut board = Board::new();
+
+        // Piece placement, rank 8 first
+        let ranks: Vec<&str> = placement.split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::BadRankCount(ranks.len()));
+        }
+
+        for (i, rank_str) in ranks.iter().enumerate() {
+            let rank = 7 - i as u8;
+            let mut file: u8 = 0;
+
+            for c in rank_str.chars() {
+                if let Some(skip) = c.to_digit(10) {
+                    if skip == 0 || skip > 8 {
+                        return Err(FenError::BadRankLength(rank + 1));
+                    }
+                    file += skip as u8;
+                } else {
+                    let piece = char_to_piece(c).ok_or(FenError::InvalidPiece(c))?;
+                    if file >= 8 {
+                        return Err(FenError::BadRankLength(rank + 1));
+                    }
+                    board.set_piece(Square(rank * 8 + file), Some(piece));
+                    file += 1;
+                }
+
+                if file > 8 {
+                    return Err(FenError::BadRankLength(rank + 1));
+                }
+            }
+
+            if file != 8 {
+                return Err(FenError::BadRankLength(rank + 1));
+            }
+        }
+
+        for color in [Color::White, Color::Black] {
+            let kings = board.bitboards[color as usize][Piece::King as usize].count_ones();
+            if kings != 1 {
+                return Err(FenError::BadKingCount(color, kings));
+            }
+        }
+
+        // Side to move
+        board.side_to_move = match side {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return Err(FenError::InvalidSideToMove(side.to_string())),
+        };
+
+        // Castling rights
+        if castling != "-" {
+            for c in castling.chars() {
+                // The right, and the squares its king and rook must still be standing on
+                let (right, color, king, rook) = match c {
+                    'K' => (&mut board.castling.white_kingside, Color::White, 4, 7),
+                    'Q' => (&mut board.castling.white_queenside, Color::White, 4, 0),
+                    'k' => (&mut board.castling.black_kingside, Color::Black, 60, 63),
+                    'q' => (&mut board.castling.black_queenside, Color::Black, 60, 56),
+                    _ => return Err(FenError::InvalidCastling(castling.to_string())),
+                };
+
+                // Each right may only appear once
+                if *right {
+                    return Err(FenError::InvalidCastling(castling.to_string()));
+                }
+                *right = true;
+
+                let home = board.pieces[king] == Some((Piece::King, color))
+                    && board.pieces[rook] == Some((Piece::Rook, color));
+                if !home {
+                    return Err(FenError::InvalidCastling(castling.to_string()));
+                }
+            }
+        }
+
+        // En passant target square: rank 6 behind a black pawn when white is to move, rank 3
+        // behind a white one when black is. It is dropped if no pawn can take there, so the
+        // position hashes the same as when it is reached by other moves
+        if en_passant != "-" {
+            let rank = if board.side_to_move == Color::White { 5 } else { 2 };
+            let sq = coord_to_square(en_passant)
+                .filter(|sq| sq.0 / 8 == rank)
+                .ok_or_else(|| FenError::InvalidEnPassant(en_passant.to_string()))?;
+            board.en_passant = Some(sq).filter(|&sq| board.en_passant_capturable(sq, board.side_to_move));
+        }
+
+        // Clocks
+        if let Some(halfmove) = fields.get(4) {
+            board.halfmove_clock = halfmove
+                .parse()
+                .map_err(|_| FenError::InvalidClock(halfmove.to_string()))?;
+        }
+
+        if let Some(fullmove) = fields.get(5) {
+            board.fullmove_number = fullmove
+                .parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| FenError::InvalidClock(fullmove.to_string()))?;
+        }
+
+        board.checkers = checkers(&board, board.side_to_move);
+        board.hash = compute_hash(&board);
+        board.pawn_hash = compute_pawn_hash(&board);
+
+        *self = board;
+        Ok(())
+    }
+
+    /// Serialize the board back into a FEN string
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        // Piece placement
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+
+            for file in 0..8 {
+                match self.pieces[rank * 8 + file] {
+                    Some((piece, color)) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(piece_to_char(piece, color));
+                    }
+                    None => empty += 1,
+                }
+            }
+
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        // Side to move
+        fen.push_str(match self.side_to_move {
+            Color::White => " w ",
+            Color::Black => " b ",
+        });
+
+        // Castling rights
+        let mut castling = String::new();
+        if self.castling.white_kingside {
+            castling.push('K');
+        }
+        if self.castling.white_queenside {
+            castling.push('Q');
+        }
+        if self.castling.black_kingside {
+            castling.push('k');
+        }
+        if self.castling.black_queenside {
+            castling.push('q');
+        }
+        if castling.is_empty() {
+            castling.push('-');
+        }
+        fen.push_str(&castling);
+
+        // En passant
+        match self.en_passant {
+            Some(sq) => fen.push_str(&format!(" {}", square_to_coord(sq.0))),
+            None => fen.push_str(" -"),
+        }
+
+        // Clocks
+        fen.push_str(&format!(" {} {}", self.halfmove_clock, self.fullmove_number));
+
+        fen
+    }
+
+    /// The position has occurred before: once if that was within the last `ply` plies (inside
+    /// the search tree), otherwise twice (threefold repetition)
+    ///
+    /// Only positions since the last capture or pawn move can match, with the same side to move.
+    pub fn is_repetition(&self, ply: usize) -> bool {
+        let reach = (self.halfmove_clock as usize).min(self.history.len());
+        let mut earlier = 0;
+
+        for distance in (4..=reach).step_by(2) {
+            if self.history[self.history.len() - distance] == self.hash {
+                if distance < ply {
+                    return true;
+                }
+
+                earlier += 1;
+                if earlier == 2 {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Is the side to move in check?
+    pub fn in_check(&self) -> bool {
+        self.checkers != 0
+    }
+
+    /// Does `side` have a pawn next to the one that double-stepped past `ep`?
+    ///
+    /// Pins are ignored, as in the Polyglot key definition.
+    pub fn en_passant_capturable(&self, ep: Square, side: Color) -> bool {
+        let rank = if side == Color::White { 4 } else { 3 };
+        let file = ep.0 % 8;
+
+        [file.checked_sub(1), Some(file + 1).filter(|&f| f < 8)]
+            .into_iter()
+            .flatten()
+            .any(|f| self.piece_at(Square(rank * 8 + f)) == Some((Piece::Pawn, side)))
+    }
+
+    /// Piece sitting on a square, if any
+    pub fn piece_at(&self, sq: Square) -> Option<(Piece, Color)> {
+        self.pieces[sq.0 as usize]
+    }
+
+    /// Place (or clear with None) a piece, keeping pieces, bitboards and occupancies in sync
+    pub fn set_piece(&mut self, sq: Square, piece: Option<(Piece, Color)>) {
+        let mask = 1u64 << sq.0;
+
+        // Remove whatever was there before
+        if let Some((old_piece, old_color)) = self.pieces[sq.0 as usize] {
+            self.bitboards[old_color as usize][old_piece as usize] &= !mask;
+            self.occupancies[old_color as usize] &= !mask;
+            self.occupancies[2] &= !mask;
+
+            let key = ZOBRIST.piece(old_piece, old_color, sq);
+            self.hash ^= key;
+            if old_piece == Piece::Pawn {
+                self.pawn_hash ^= key;
+            }
+
+            self.psqt[old_color as usize] -= pst::value(old_piece, old_color, sq.0);
+        }
+
+        if let Some((new_piece, new_color)) = piece {
+            self.bitboards[new_color as usize][new_piece as usize] |= mask;
+            self.occupancies[new_color as usize] |= mask;
+            self.occupancies[2] |= mask;
+
+            let key = ZOBRIST.piece(new_piece, new_color, sq);
+            self.hash ^= key;
+            if new_piece == Piece::Pawn {
+                self.pawn_hash ^= key;
+            }
+
+            self.psqt[new_color as usize] += pst::value(new_piece, new_color, sq.0);
+        }
+
+        self.pieces[sq.0 as usize] = piece;
+    }
+
+    /// Update castling rights, en passant square and clocks once the pieces have moved
+    pub fn update_state_after_move(
+        &mut self,
+        from: Square,
+        to: Square,
+        piece: Piece,
+        is_capture: bool,
+        is_double_push: bool,
+    ) {
+        // Take the old castling and en passant keys out; the new ones go back in below
+        self.hash ^= ZOBRIST.castling(self.castling) ^ ZOBRIST.en_passant(self.en_passant);
+
+        // Moving the king or a rook off its home square, or capturing on one, kills that right
+        for sq in [from.0, to.0] {
+            match sq {
+                0 => self.castling.white_queenside = false,
+                4 => {
+                    self.castling.white_kingside = false;
+                    self.castling.white_queenside = false;
+                }
+                7 => self.castling.white_kingside = false,
+                56 => self.castling.black_queenside = false,
+                60 => {
+                    self.castling.black_kingside = false;
+                    self.castling.black_queenside = false;
+                }
+                63 => self.castling.black_kingside = false,
+                _ => {}
+            }
+        }
+
+        // En passant target is the square the pawn skipped over, kept only if an enemy pawn
+        // can take there so that transpositions share a key
+        self.en_passant = if is_double_push {
+            Some(Square((from.0 + to.0) / 2)).filter(|&ep| self.en_passant_capturable(ep, self.side_to_move.opposite()))
+        } else {
+            None
+        };
+
+        self.hash ^= ZOBRIST.castling(self.castling) ^ ZOBRIST.en_passant(self.en_passant);
+
+        if piece == Piece::Pawn || is_capture {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+
+        if self.side_to_move == Color::Black {
+            self.fullmove_number += 1;
+        }
+    }
+
+    /// Put back the irreversible state saved before a move
+    pub fn restore_state(&mut self, state: &GameState) {
+        state.restore_state(self);
+    }
+
+    /// Print board for debugging
+    pub fn print(&self) {
+        println!("  a b c d e f g h");
+        for rank in (0..8).rev() {
+            print!("{} ", rank + 1);
+            for file in 0..8 {
+                let idx = rank * 8 + file;
+                let symbol = match self.pieces[idx as usize] {
+                    Some((piece, color)) => piece_to_char(piece, color),
+                    None => '.',
+                };
+                print!("{} ", symbol);
+            }
+            println!();
+        }
+        println!();
+    }
+}
+
+fn piece_to_char(p: Piece, c: Color) -> char {
+    match (p, c) {
+        (Piece::Pawn, Color::White) => 'P',
+        (Piece::Knight, Color::White) => 'N',
+        (Piece::Bishop, Color::White) => 'B',
+        (Piece::Rook, Color::White) => 'R',
+        (Piece::Queen, Color::White) => 'Q',
+        (Piece::King, Color::White) => 'K',
+        (Piece::Pawn, Color::Black) => 'p',
+        (Piece::Knight, Color::Black) => 'n',
+        (Piece::Bishop, Color::Black) => 'b',
+        (Piece::Rook, Color::Black) => 'r',
+        (Piece::Queen, Color::Black) => 'q',
+        (Piece::King, Color::Black) => 'k',
+    }
+}
+
+fn char_to_piece(c: char) -> Option<(Piece, Color)> {
+    match c {
+        'P' => Some((Piece::Pawn, Color::White)),
+        'N' => Some((Piece::Knight, Color::White)),
+        'B' => Some((Piece::Bishop, Color::White)),
+        'R' => Some((Piece::Rook, Color::White)),
+        'Q' => Some((Piece::Queen, Color::White)),
+        'K' => Some((Piece::King, Color::White)),
+        'p' => Some((Piece::Pawn, Color::Black)),
+        'n' => Some((Piece::Knight, Color::Black)),
+        'b' => Some((Piece::Bishop, Color::Black)),
+        'r' => Some((Piece::Rook, Color::Black)),
+        'q' => Some((Piece::Queen, Color::Black)),
+        'k' => Some((Piece::King, Color::Black)),
+        _ => None,
+    }
+}
+
+// Convert algebraic notation (e.g. "e3") to a square
+pub fn coord_to_square(coord: &str) -> Option<Square> {
+    let bytes = coord.as_bytes();
+    if bytes.len() != 2 {
+        return None;
+    }
+
+    let file = bytes[0].wrapping_sub(b'a');
+    let rank = bytes[1].wrapping_sub(b'1');
+    if file > 7 || rank > 7 {
+        return None;
+    }
+
+    Some(Square(rank * 8 + file))
+}
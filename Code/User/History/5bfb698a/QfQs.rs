@@ -0,0 +1,371 @@
+use std::fmt;
+
+use crate::movegen::pawns::Bitboard;
+use crate::state::r#move::square_to_coord;
+
+/// Standard starting position
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Color {
+    White,
+    Black,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Piece {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Square(pub u8); // 0..63
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CastlingRights {
+    pub white_kingside: bool,
+    pub white_queenside: bool,
+    pub black_kingside: bool,
+    pub black_queenside: bool,
+}
+
+/// Reasons a FEN string can be rejected by `Board::load_fen`
+#[derive(Clone, PartialEq, Debug)]
+pub enum FenError {
+    MissingField(&'static str),
+    TooManyFields(usize),
+    BadRankCount(usize),
+    BadRankLength(u8),             // rank number (1..8) that did not add up to 8 files
+    InvalidPiece(char),
+    InvalidSideToMove(String),
+    InvalidCastling(String),
+    InvalidEnPassant(String),
+    InvalidClock(String),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FenError::MissingField(field) => write!(f, "missing {} field", field),
+            FenError::TooManyFields(n) => write!(f, "expected at most 6 fields, found {}", n),
+            FenError::BadRankCount(n) => write!(f, "expected 8 ranks, found {}", n),
+            FenError::BadRankLength(rank) => write!(f, "rank {} does not describe 8 files", rank),
+            FenError::InvalidPiece(c) => write!(f, "invalid piece character '{}'", c),
+            FenError::InvalidSideToMove(s) => write!(f, "invalid side to move '{}'", s),
+            FenError::InvalidCastling(s) => write!(f, "invalid castling rights '{}'", s),
+            FenError::InvalidEnPassant(s) => write!(f, "invalid en passant square '{}'", s),
+            FenError::InvalidClock(s) => write!(f, "invalid move clock '{}'", s),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+pub struct Board {
+    pub pieces: [Option<(Piece, Color)>; 64], // Square-wise representation
+    pub bitboards: [[Bitboard; 6]; 2],        // [color][piece_type]
+    pub occupancies: [Bitboard; 3],           // [white, black, all]
+
+    pub side_to_move: Color,
+    pub castling: CastlingRights,
+    pub en_passant: Option<Square>,
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+}
+
+impl Board {
+    pub fn new() -> Self {
+        // Create an empty board (you can override with FEN later)
+        Self {
+            pieces: [None; 64],
+            bitboards: [[0; 6]; 2],
+            occupancies: [0; 3],
+            side_to_move: Color::White,
+            castling: CastlingRights {
+                white_kingside: false,
+                white_queenside: false,
+                black_kingside: false,
+                black_queenside: false,
+            },
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    /// Build a board straight from a FEN string
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let mut board = Board::new();
+        board.load_fen(fen)?;
+        Ok(board)
+    }
+
+    /// Load a FEN string into the board
+    /// The halfmove and fullmove clocks may be omitted (defaulting to 0 and 1).
+    /// On error the board is left untouched.
+    pub fn load_fen(&mut self, fen: &str) -> Result<(), FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() > 6 {
+            return Err(FenError::TooManyFields(fields.len()));
+        }
+
+        let placement = *fields.first().ok_or(FenError::MissingField("piece placement"))?;
+        let side = *fields.get(1).ok_or(FenError::MissingField("side to move"))?;
+        let castling = *fields.get(2).ok_or(FenError::MissingField("castling"))?;
+        let en_passant = *fields.get(3).ok_or(FenError::MissingField("en passant"))?;
+
+        // Build into a scratch board so a bad FEN never leaves us half-loaded
+        let mut board = Board::new();
+
+        // Piece placement, rank 8 first
+        let ranks: Vec<&str> = placement.split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::BadRankCount(ranks.len()));
+        }
+
+        for (i, rank_str) in ranks.iter().enumerate() {
+            let rank = 7 - i as u8;
+            let mut file: u8 = 0;
+
+            for c in rank_str.chars() {
+                if let Some(skip) = c.to_digit(10) {
+                    if skip == 0 || skip > 8 {
+                        return Err(FenError::BadRankLength(rank + 1));
+                    }
+                    file += skip as u8;
+                } else {
+                    let piece = char_to_piece(c).ok_or(FenError::InvalidPiece(c))?;
+                    if file >= 8 {
+                        return Err(FenError::BadRankLength(rank + 1));
+                    }
+                    board.set_piece(Square(rank * 8 + file), Some(piece));
+                    file += 1;
+                }
+
+                if file > 8 {
+                    return Err(FenError::BadRankLength(rank + 1));
+                }
+            }
+
+            if file != 8 {
+                return Err(FenError::BadRankLength(rank + 1));
+            }
+        }
+
+        // Side to move
+        board.side_to_move = match side {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return Err(FenError::InvalidSideToMove(side.to_string())),
+        };
+
+        // Castling rights
+        if castling != "-" {
+            for c in castling.chars() {
+                let right = match c {
+                    'K' => &mut board.castling.white_kingside,
+                    'Q' => &mut board.castling.white_queenside,
+                    'k' => &mut board.castling.black_kingside,
+                    'q' => &mut board.castling.black_queenside,
+                    _ => return Err(FenError::InvalidCastling(castling.to_string())),
+                };
+
+                // Each right may only appear once
+                if *right {
+                    return Err(FenError::InvalidCastling(castling.to_string()));
+                }
+                *right = true;
+            }
+        }
+
+        // En passant target square (must be on rank 3 or 6)
+        if en_passant != "-" {
+            let sq = coord_to_square(en_passant)
+                .filter(|sq| sq.0 / 8 == 2 || sq.0 / 8 == 5)
+                .ok_or_else(|| FenError::InvalidEnPassant(en_passant.to_string()))?;
+            board.en_passant = Some(sq);
+        }
+
+        // Clocks
+        if let Some(halfmove) = fields.get(4) {
+            board.halfmove_clock = halfmove
+                .parse()
+                .map_err(|_| FenError::InvalidClock(halfmove.to_string()))?;
+        }
+
+        if let Some(fullmove) = fields.get(5) {
+            board.fullmove_number = fullmove
+                .parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| FenError::InvalidClock(fullmove.to_string()))?;
+        }
+
+        *self = board;
+        Ok(())
+    }
+
+    /// Serialize the board back into a FEN string
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        // Piece placement
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+
+            for file in 0..8 {
+                match self.pieces[rank * 8 + file] {
+                    Some((piece, color)) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(piece_to_char(piece, color));
+                    }
+                    None => empty += 1,
+                }
+            }
+
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        // Side to move
+        fen.push_str(match self.side_to_move {
+            Color::White => " w ",
+            Color::Black => " b ",
+        });
+
+        // Castling rights
+        let mut castling = String::new();
+        if self.castling.white_kingside {
+            castling.push('K');
+        }
+        if self.castling.white_queenside {
+            castling.push('Q');
+        }
+        if self.castling.black_kingside {
+            castling.push('k');
+        }
+        if self.castling.black_queenside {
+            castling.push('q');
+        }
+        if castling.is_empty() {
+            castling.push('-');
+        }
+        fen.push_str(&castling);
+
+        // En passant
+        match self.en_passant {
+            Some(sq) => fen.push_str(&format!(" {}", square_to_coord(sq.0))),
+            None => fen.push_str(" -"),
+        }
+
+        // Clocks
+        fen.push_str(&format!(" {} {}", self.halfmove_clock, self.fullmove_number));
+
+        fen
+    }
+
+    /// Piece sitting on a square, if any
+    pub fn piece_at(&self, sq: Square) -> Option<(Piece, Color)> {
+        self.pieces[sq.0 as usize]
+    }
+
+    /// Place (or clear with None) a piece, keeping pieces, bitboards and occupancies in sync
+    pub fn set_piece(&mut self, sq: Square, piece: Option<(Piece, Color)>) {
+        let mask = 1u64 << sq.0;
+
+        // Remove whatever was there before
+        if let Some((old_piece, old_color)) = self.pieces[sq.0 as usize] {
+            self.bitboards[old_color as usize][old_piece as usize] &= !mask;
+            self.occupancies[old_color as usize] &= !mask;
+            self.occupancies[2] &= !mask;
+        }
+
+        if let Some((new_piece, new_color)) = piece {
+            self.bitboards[new_color as usize][new_piece as usize] |= mask;
+            self.occupancies[new_color as usize] |= mask;
+            self.occupancies[2] |= mask;
+        }
+
+        self.pieces[sq.0 as usize] = piece;
+    }
+
+    /// Print board for debugging
+    pub fn print(&self) {
+        println!("  a b c d e f g h");
+        for rank in (0..8).rev() {
+            print!("{} ", rank + 1);
+            for file in 0..8 {
+                let idx = rank * 8 + file;
+                let symbol = match self.pieces[idx as usize] {
+                    Some((piece, color)) => piece_to_char(piece, color),
+                    None => '.',
+                };
+                print!("{} ", symbol);
+            }
+            println!();
+        }
+        println!();
+    }
+}
+
+fn piece_to_char(p: Piece, c: Color) -> char {
+    match (p, c) {
+        (Piece::Pawn, Color::White) => 'P',
+        (Piece::Knight, Color::White) => 'N',
+        (Piece::Bishop, Color::White) => 'B',
+        (Piece::Rook, Color::White) => 'R',
+        (Piece::Queen, Color::White) => 'Q',
+        (Piece::King, Color::White) => 'K',
+        (Piece::Pawn, Color::Black) => 'p',
+        (Piece::Knight, Color::Black) => 'n',
+        (Piece::Bishop, Color::Black) => 'b',
+        (Piece::Rook, Color::Black) => 'r',
+        (Piece::Queen, Color::Black) => 'q',
+        (Piece::King, Color::Black) => 'k',
+    }
+}
+
+fn char_to_piece(c: char) -> Option<(Piece, Color)> {
+    match c {
+        'P' => Some((Piece::Pawn, Color::White)),
+        'N' => Some((Piece::Knight, Color::White)),
+        'B' => Some((Piece::Bishop, Color::White)),
+        'R' => Some((Piece::Rook, Color::White)),
+        'Q' => Some((Piece::Queen, Color::White)),
+        'K' => Some((Piece::King, Color::White)),
+        'p' => Some((Piece::Pawn, Color::Black)),
+        'n' => Some((Piece::Knight, Color::Black)),
+        'b' => Some((Piece::Bishop, Color::Black)),
+        'r' => Some((Piece::Rook, Color::Black)),
+        'q' => Some((Piece::Queen, Color::Black)),
+        'k' => Some((Piece::King, Color::Black)),
+        _ => None,
+    }
+}
+
+// Convert algebraic notation (e.g. "e3") to a square
+pub fn coord_to_square(coord: &str) -> Option<Square> {
+    let bytes = coord.as_bytes();
+    if bytes.len() != 2 {
+        return None;
+    }
+
+    let file = bytes[0].wrapping_sub(b'a');
+    let rank = bytes[1].wrapping_sub(b'1');
+    if file > 7 || rank > 7 {
+        return None;
+    }
+
+    Some(Square(rank * 8 + file))
+}
@@ -0,0 +1,112 @@
+// Perft reference positions from the chessprogramming wiki, run through the binary.
+// Depths are kept small enough for a debug build.
+
+use std::process::Command;
+
+fn perft(fen: &str, depth: u32) -> u64 {
+    let output = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .args(["perft", &depth.to_string(), fen])
+        .output()
+        .expect("failed to run chess-engine");
+
+    assert!(output.status.success(), "perft exited with {}", output.status);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Nodes: "))
+        .and_then(|n| n.trim().parse().ok())
+        .expect("no node count in perft output")
+}
+
+#[test]
+fn startpos() {
+    let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    assert_eq!(perft(fen, 1), 20);
+    assert_eq!(perft(fen, 2), 400);
+    assert_eq!(perft(fen, 3), 8902);
+    assert_eq!(perft(fen, 4), 197281);
+}
+
+#[test]
+fn kiwipete() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    assert_eq!(perft(fen, 1), 48);
+    assert_eq!(perft(fen, 2), 2039);
+    assert_eq!(perft(fen, 3), 97862);
+}
+
+#[test]
+fn position_3() {
+    let fen = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+    assert_eq!(perft(fen, 1), 14);
+    assert_eq!(perft(fen, 2), 191);
+    assert_eq!(perft(fen, 3), 2812);
+    assert_eq!(perft(fen, 4), 43238);
+    assert_eq!(perft(fen, 5), 674624);
+}
+
+#[test]
+fn position_4() {
+    let fen = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+    assert_eq!(perft(fen, 1), 6);
+    assert_eq!(perft(fen, 2), 264);
+    assert_eq!(perft(fen, 3), 9467);
+    assert_eq!(perft(fen, 4), 422333);
+}
+
+#[test]
+fn position_4_mirrored() {
+    let fen = "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1";
+    assert_eq!(perft(fen, 1), 6);
+    assert_eq!(perft(fen, 2), 264);
+    assert_eq!(perft(fen, 3), 9467);
+    assert_eq!(perft(fen, 4), 422333);
+}
+
+#[test]
+fn position_5() {
+    let fen = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+    assert_eq!(perft(fen, 1), 44);
+    assert_eq!(perft(fen, 2), 1486);
+    assert_eq!(perft(fen, 3), 62379);
+}
+
+#[test]
+fn position_6() {
+    let fen = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+    assert_eq!(perft(fen, 1), 46);
+    assert_eq!(perft(fen, 2), 2079);
+    assert_eq!(perft(fen, 3), 89890);
+}
+
+#[test]
+fn built_in_suite_passes() {
+    let status = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .args(["suite", "3"])
+        .output()
+        .expect("failed to run chess-engine")
+        .status;
+
+    assert!(status.success());
+}
+
+#[test]
+fn divide_sums_to_perft() {
+    let output = Command::new(env!("CARGO_BIN_EXE_chess-engine"))
+        .args(["divide", "3"])
+        .output()
+        .expect("failed to run chess-engine");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // "e2e4: 600" lines, then the total
+    let sum: u64 = stdout
+        .lines()
+        .filter(|line| !line.starts_with("Nodes") && !line.starts_with("Time") && !line.starts_with("NPS"))
+        .filter_map(|line| line.split(": ").nth(1))
+        .filter_map(|n| n.parse::<u64>().ok())
+        .sum();
+
+    assert_eq!(sum, 8902);
+    assert!(stdout.contains("Nodes: 8902"));
+}
@@ -0,0 +1,264 @@
+use crate::movegen::bishops::BISHOP_MAGIC;
+use crate::movegen::pawns::{self, Bitboard, BLACK_ATTACKING, WHITE_ATTACKING};
+use crate::movegen::{kings, knights, queens, rooks};
+use crate::state::board::{Board, Color, Piece, Square};
+use crate::state::make_move::make_move;
+use crate::state::r#move::{
+    encode_move, Move, FLAG_CAPTURE, FLAG_CASTLING, FLAG_DOUBLE_PAWN_PUSH, FLAG_EN_PASSANT,
+    FLAG_PROMOTION, FLAG_QUIET, PROMO_B, PROMO_N, PROMO_NONE, PROMO_Q, PROMO_R,
+};
+use crate::state::state::GameState;
+use crate::state::undo_move::undo_move;
+
+// No legal chess position has more than 218 moves
+const MAX_MOVES: usize = 256;
+
+/// Fixed-capacity list of encoded moves (no heap allocation per node)
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    count: usize,
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        Self {
+            moves: [0; MAX_MOVES],
+            count: 0,
+        }
+    }
+
+    pub fn push(&mut self, m: Move) {
+        self.moves[self.count] = m;
+        self.count += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn as_slice(&self) -> &[Move] {
+        &self.moves[..self.count]
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Move> {
+        self.as_slice().iter()
+    }
+}
+
+/// All legal moves for the side to move
+pub fn generate_legal_moves(board: &Board) -> MoveList {
+    let pseudo = generate_pseudo_legal_moves(board);
+    let mut legal = MoveList::new();
+
+    // Play each move on a scratch copy and drop the ones that leave our king attacked
+    let mut scratch = board.clone();
+    let mut state = GameState::new();
+    let us = board.side_to_move;
+
+    for &m in pseudo.iter() {
+        if !make_move(&mut scratch, m, &mut state) {
+            continue;
+        }
+        if !king_attacked(&scratch, us) {
+            legal.push(m);
+        }
+        undo_move(&mut scratch, m, &state);
+    }
+
+    legal
+}
+
+/// Moves that obey piece movement rules but may leave our own king in check
+pub fn generate_pseudo_legal_moves(board: &Board) -> MoveList {
+    let mut list = MoveList::new();
+    let us = board.side_to_move;
+    let them = us.opposite();
+    let friendlies = board.occupancies[us as usize];
+    let enemies = board.occupancies[them as usize];
+    let occupied = board.occupancies[2];
+
+    generate_pawn_moves(board, &mut list);
+
+    // Knights
+    let mut knights_bb = board.bitboards[us as usize][Piece::Knight as usize];
+    while knights_bb != 0 {
+        let from = knights_bb.trailing_zeros() as u8;
+        let targets = knights::generate_moves(1u64 << from, friendlies);
+        push_targets(&mut list, from, targets, enemies);
+        knights_bb &= knights_bb - 1;
+    }
+
+    // Bishops
+    let mut bishops_bb = board.bitboards[us as usize][Piece::Bishop as usize];
+    while bishops_bb != 0 {
+        let from = bishops_bb.trailing_zeros() as u8;
+        let targets = BISHOP_MAGIC.bishop_moves(1u64 << from, occupied) & !friendlies;
+        push_targets(&mut list, from, targets, enemies);
+        bishops_bb &= bishops_bb - 1;
+    }
+
+    // Rooks
+    let mut rooks_bb = board.bitboards[us as usize][Piece::Rook as usize];
+    while rooks_bb != 0 {
+        let from = rooks_bb.trailing_zeros() as u8;
+        let targets = rooks::rook_moves(1u64 << from, occupied) & !friendlies;
+        push_targets(&mut list, from, targets, enemies);
+        rooks_bb &= rooks_bb - 1;
+    }
+
+    // Queens
+    let mut queens_bb = board.bitboards[us as usize][Piece::Queen as usize];
+    while queens_bb != 0 {
+        let from = queens_bb.trailing_zeros() as u8;
+        let targets = queens::queen_moves(1u64 << from, occupied) & !friendlies;
+        push_targets(&mut list, from, targets, enemies);
+        queens_bb &= queens_bb - 1;
+    }
+
+    // King
+    let king_bb = board.bitboards[us as usize][Piece::King as usize];
+    if king_bb != 0 {
+        let from = king_bb.trailing_zeros() as u8;
+        let targets = kings::generate_moves(king_bb, friendlies);
+        push_targets(&mut list, from, targets, enemies);
+        generate_castling_moves(board, from, &mut list);
+    }
+
+    list
+}
+
+// Emit one move per target square, flagged as a capture when it lands on an enemy
+fn push_targets(list: &mut MoveList, from: u8, targets: Bitboard, enemies: Bitboard) {
+    let mut bb = targets;
+
+    while bb != 0 {
+        let to = bb.trailing_zeros() as u8;
+        let flag = if enemies & (1u64 << to) != 0 { FLAG_CAPTURE } else { FLAG_QUIET };
+        list.push(encode_move(from, to, flag, PROMO_NONE));
+        bb &= bb - 1;
+    }
+}
+
+fn generate_pawn_moves(board: &Board, list: &mut MoveList) {
+    let us = board.side_to_move;
+    let is_white = us == Color::White;
+    let friendlies = board.occupancies[us as usize];
+    let enemies = board.occupancies[us.opposite() as usize];
+    let promotion_rank = if is_white { 7 } else { 0 };
+
+    let mut pawns_bb = board.bitboards[us as usize][Piece::Pawn as usize];
+    while pawns_bb != 0 {
+        let from = pawns_bb.trailing_zeros() as u8;
+        let mut targets = pawns::generate_pawn_moves(1u64 << from, friendlies, enemies, is_white);
+
+        while targets != 0 {
+            let to = targets.trailing_zeros() as u8;
+
+            if to / 8 == promotion_rank {
+                for promo in [PROMO_Q, PROMO_R, PROMO_B, PROMO_N] {
+                    list.push(encode_move(from, to, FLAG_PROMOTION, promo));
+                }
+            } else if enemies & (1u64 << to) != 0 {
+                list.push(encode_move(from, to, FLAG_CAPTURE, PROMO_NONE));
+            } else if from.abs_diff(to) == 16 {
+                list.push(encode_move(from, to, FLAG_DOUBLE_PAWN_PUSH, PROMO_NONE));
+            } else {
+                list.push(encode_move(from, to, FLAG_QUIET, PROMO_NONE));
+            }
+
+            targets &= targets - 1;
+        }
+
+        // En passant capture onto the square the enemy pawn skipped
+        if let Some(Square(ep)) = board.en_passant {
+            let attacks = if is_white {
+                WHITE_ATTACKING[from as usize]
+            } else {
+                BLACK_ATTACKING[from as usize]
+            };
+            if attacks & (1u64 << ep) != 0 {
+                list.push(encode_move(from, ep, FLAG_EN_PASSANT, PROMO_NONE));
+            }
+        }
+
+        pawns_bb &= pawns_bb - 1;
+    }
+}
+
+fn generate_castling_moves(board: &Board, king_sq: u8, list: &mut MoveList) {
+    let us = board.side_to_move;
+    let them = us.opposite();
+    let occupied = board.occupancies[2];
+
+    // (right, king start, king target, squares that must be empty, squares the king crosses)
+    let candidates = match us {
+        Color::White => [
+            (board.castling.white_kingside, 4, 6, 0x0000_0000_0000_0060u64, [4, 5, 6]),
+            (board.castling.white_queenside, 4, 2, 0x0000_0000_0000_000Eu64, [4, 3, 2]),
+        ],
+        Color::Black => [
+            (board.castling.black_kingside, 60, 62, 0x6000_0000_0000_0000u64, [60, 61, 62]),
+            (board.castling.black_queenside, 60, 58, 0x0E00_0000_0000_0000u64, [60, 59, 58]),
+        ],
+    };
+
+    for (allowed, from, to, empty, path) in candidates {
+        if !allowed || king_sq != from || occupied & empty != 0 {
+            continue;
+        }
+
+        // The king may not castle out of, through, or into check
+        if path.iter().any(|&sq| is_attacked(board, sq, them)) {
+            continue;
+        }
+
+        list.push(encode_move(from, to, FLAG_CASTLING, PROMO_NONE));
+    }
+}
+
+// Is our king attacked after the move has been played?
+fn king_attacked(board: &Board, color: Color) -> bool {
+    let king_bb = board.bitboards[color as usize][Piece::King as usize];
+    if king_bb == 0 {
+        return false;
+    }
+
+    is_attacked(board, king_bb.trailing_zeros() as u8, color.opposite())
+}
+
+// Does any piece of `by` attack `sq`?
+fn is_attacked(board: &Board, sq: u8, by: Color) -> bool {
+    let pieces = &board.bitboards[by as usize];
+    let occupied = board.occupancies[2];
+    let target = 1u64 << sq;
+
+    // A pawn of `by` attacks sq if a pawn of the other colour on sq would attack it back
+    let pawn_attacks = match by {
+        Color::White => BLACK_ATTACKING[sq as usize],
+        Color::Black => WHITE_ATTACKING[sq as usize],
+    };
+    if pawn_attacks & pieces[Piece::Pawn as usize] != 0 {
+        return true;
+    }
+
+    if knights::ATTACKING[sq as usize] & pieces[Piece::Knight as usize] != 0 {
+        return true;
+    }
+
+    if kings::KING_ATTACKS[sq as usize] & pieces[Piece::King as usize] != 0 {
+        return true;
+    }
+
+    let rook_like = pieces[Piece::Rook as usize] | pieces[Piece::Queen as usize];
+    if rook_like != 0 && rooks::rook_moves(target, occupied) & rook_like != 0 {
+        return true;
+    }
+
+    let bishop_like = pieces[Piece::Bishop as usize] | pieces[Piece::Queen as usize];
+    bishop_like != 0 && BISHOP_MAGIC.bishop_moves(target, occupied) & bishop_like != 0
+}
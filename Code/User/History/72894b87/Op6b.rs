@@ -0,0 +1,321 @@
+use crate::movegen::attacks::{attackers_to, is_square_attacked, BETWEEN, LINE};
+use crate::movegen::pawns::{self, Bitboard, BLACK_ATTACKING, WHITE_ATTACKING};
+use crate::movegen::queens::{self, BISHOP_MAGICS_TABLE};
+use crate::movegen::rooks::{self, ROOK_MAGICS_TABLE};
+use crate::movegen::{kings, knights};
+use crate::state::board::{Board, Color, Piece, Square};
+use crate::state::r#move::{
+    encode_move, Move, FLAG_CAPTURE, FLAG_CASTLING, FLAG_DOUBLE_PAWN_PUSH, FLAG_EN_PASSANT,
+    FLAG_PROMOTION, FLAG_PROMOTION_CAPTURE, FLAG_QUIET, PROMO_B, PROMO_N, PROMO_NONE, PROMO_Q, PROMO_R,
+};
+
+// No legal chess position has more than 218 moves
+const MAX_MOVES: usize = 256;
+
+/// Fixed-capacity list of encoded moves (no heap allocation per node)
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    count: usize,
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        Self {
+            moves: [0; MAX_MOVES],
+            count: 0,
+        }
+    }
+
+    pub fn push(&mut self, m: Move) {
+        self.moves[self.count] = m;
+        self.count += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn as_slice(&self) -> &[Move] {
+        &self.moves[..self.count]
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Move> {
+        self.as_slice().iter()
+    }
+}
+
+/// Pins and check information for the side to move, worked out once per position
+struct Restrictions {
+    king_sq: u8,
+    check_mask: Bitboard, // squares a non-king move must land on (all ones when not in check)
+    pinned: Bitboard,     // our pieces that may only move along the line to the king
+}
+
+/// All legal moves for the side to move
+///
+/// Legality is decided up front from the pinned pieces and the check-evasion mask,
+/// so no move has to be played to find out whether it leaves the king in check.
+pub fn generate_legal_moves(board: &Board) -> MoveList {
+    let mut list = MoveList::new();
+    let us = board.side_to_move;
+    let them = us.opposite();
+    let friendlies = board.occupancies[us as usize];
+    let enemies = board.occupancies[them as usize];
+    let occupied = board.occupancies[2];
+
+    let king_bb = board.bitboards[us as usize][Piece::King as usize];
+    if king_bb == 0 {
+        return list;
+    }
+    let king_sq = king_bb.trailing_zeros() as u8;
+
+    // King steps: the destination must be safe with the king itself lifted off the board,
+    // so it cannot hide behind its own body along a slider's line
+    let mut targets = kings::generate_moves(king_bb, friendlies);
+    while targets != 0 {
+        let to = targets.trailing_zeros() as u8;
+        if attackers_to(board, to, occupied ^ king_bb) & enemies == 0 {
+            let flag = if enemies & (1u64 << to) != 0 { FLAG_CAPTURE } else { FLAG_QUIET };
+            list.push(encode_move(king_sq, to, flag, PROMO_NONE));
+        }
+        targets &= targets - 1;
+    }
+
+    // Double check: only the king can move
+    if board.checkers.count_ones() > 1 {
+        return list;
+    }
+
+    let check_mask = if board.checkers != 0 {
+        let checker_sq = board.checkers.trailing_zeros() as usize;
+        BETWEEN[king_sq as usize][checker_sq] | board.checkers
+    } else {
+        generate_castling_moves(board, king_sq, &mut list);
+        !0
+    };
+
+    let restrictions = Restrictions {
+        king_sq,
+        check_mask,
+        pinned: pinned_pieces(board, king_sq),
+    };
+
+    generate_pawn_moves(board, &restrictions, &mut list);
+
+    // Knights (a pinned knight can never move)
+    let mut knights_bb = board.bitboards[us as usize][Piece::Knight as usize] & !restrictions.pinned;
+    while knights_bb != 0 {
+        let from = knights_bb.trailing_zeros() as u8;
+        let targets = knights::generate_moves(1u64 << from, friendlies) & check_mask;
+        push_targets(&mut list, from, targets, enemies);
+        knights_bb &= knights_bb - 1;
+    }
+
+    // Bishops
+    let mut bishops_bb = board.bitboards[us as usize][Piece::Bishop as usize];
+    while bishops_bb != 0 {
+        let from = bishops_bb.trailing_zeros() as u8;
+        let targets = BISHOP_MAGICS_TABLE[from as usize].get_attacks(occupied) & !friendlies;
+        push_targets(&mut list, from, restrict(&restrictions, from, targets), enemies);
+        bishops_bb &= bishops_bb - 1;
+    }
+
+    // Rooks
+    let mut rooks_bb = board.bitboards[us as usize][Piece::Rook as usize];
+    while rooks_bb != 0 {
+        let from = rooks_bb.trailing_zeros() as u8;
+        let targets = rooks::rook_moves(1u64 << from, occupied) & !friendlies;
+        push_targets(&mut list, from, restrict(&restrictions, from, targets), enemies);
+        rooks_bb &= rooks_bb - 1;
+    }
+
+    // Queens
+    let mut queens_bb = board.bitboards[us as usize][Piece::Queen as usize];
+    while queens_bb != 0 {
+        let from = queens_bb.trailing_zeros() as u8;
+        let targets = queens::queen_moves(1u64 << from, occupied) & !friendlies;
+        push_targets(&mut list, from, restrict(&restrictions, from, targets), enemies);
+        queens_bb &= queens_bb - 1;
+    }
+
+    list
+}
+
+// Our pieces standing alone between the king and an enemy slider
+fn pinned_pieces(board: &Board, king_sq: u8) -> Bitboard {
+    let us = board.side_to_move;
+    let them = &board.bitboards[us.opposite() as usize];
+    let their_occupancy = board.occupancies[us.opposite() as usize];
+
+    // Enemy sliders that would hit the king if only their own pieces blocked
+    let rook_like = them[Piece::Rook as usize] | them[Piece::Queen as usize];
+    let bishop_like = them[Piece::Bishop as usize] | them[Piece::Queen as usize];
+    let mut snipers = (ROOK_MAGICS_TABLE[king_sq as usize].attacks(their_occupancy) & rook_like)
+        | (BISHOP_MAGICS_TABLE[king_sq as usize].get_attacks(their_occupancy) & bishop_like);
+
+    let mut pinned = 0;
+    while snipers != 0 {
+        let sniper_sq = snipers.trailing_zeros() as usize;
+        let blockers = BETWEEN[king_sq as usize][sniper_sq] & board.occupancies[2];
+
+        if blockers.count_ones() == 1 {
+            pinned |= blockers & board.occupancies[us as usize];
+        }
+        snipers &= snipers - 1;
+    }
+
+    pinned
+}
+
+// Cut a non-king piece's targets down to check evasions and, if pinned, its pin ray
+fn restrict(restrictions: &Restrictions, from: u8, targets: Bitboard) -> Bitboard {
+    let mut targets = targets & restrictions.check_mask;
+
+    if restrictions.pinned & (1u64 << from) != 0 {
+        targets &= LINE[restrictions.king_sq as usize][from as usize];
+    }
+
+    targets
+}
+
+// Emit one move per target square, flagged as a capture when it lands on an enemy
+fn push_targets(list: &mut MoveList, from: u8, targets: Bitboard, enemies: Bitboard) {
+    let mut bb = targets;
+
+    while bb != 0 {
+        let to = bb.trailing_zeros() as u8;
+        let flag = if enemies & (1u64 << to) != 0 { FLAG_CAPTURE } else { FLAG_QUIET };
+        list.push(encode_move(from, to, flag, PROMO_NONE));
+        bb &= bb - 1;
+    }
+}
+
+fn generate_pawn_moves(board: &Board, restrictions: &Restrictions, list: &mut MoveList) {
+    let us = board.side_to_move;
+    let is_white = us == Color::White;
+    let friendlies = board.occupancies[us as usize];
+    let enemies = board.occupancies[us.opposite() as usize];
+    let promotion_rank = if is_white { 7 } else { 0 };
+
+    let mut pawns_bb = board.bitboards[us as usize][Piece::Pawn as usize];
+    while pawns_bb != 0 {
+        let from = pawns_bb.trailing_zeros() as u8;
+        let targets = pawns::generate_pawn_moves(1u64 << from, friendlies, enemies, is_white);
+        let mut targets = restrict(restrictions, from, targets);
+
+        while targets != 0 {
+            let to = targets.trailing_zeros() as u8;
+
+            if to / 8 == promotion_rank {
+                let flag = if enemies & (1u64 << to) != 0 { FLAG_PROMOTION_CAPTURE } else { FLAG_PROMOTION };
+                for promo in [PROMO_Q, PROMO_R, PROMO_B, PROMO_N] {
+                    list.push(encode_move(from, to, flag, promo));
+                }
+            } else if enemies & (1u64 << to) != 0 {
+                list.push(encode_move(from, to, FLAG_CAPTURE, PROMO_NONE));
+            } else if from.abs_diff(to) == 16 {
+                list.push(encode_move(from, to, FLAG_DOUBLE_PAWN_PUSH, PROMO_NONE));
+            } else {
+                list.push(encode_move(from, to, FLAG_QUIET, PROMO_NONE));
+            }
+
+            targets &= targets - 1;
+        }
+
+        // En passant capture onto the square the enemy pawn skipped
+        if let Some(Square(ep)) = board.en_passant {
+            let attacks = if is_white {
+                WHITE_ATTACKING[from as usize]
+            } else {
+                BLACK_ATTACKING[from as usize]
+            };
+            if attacks & (1u64 << ep) != 0 && en_passant_is_legal(board, restrictions.king_sq, from, ep) {
+                list.push(encode_move(from, ep, FLAG_EN_PASSANT, PROMO_NONE));
+            }
+        }
+
+        pawns_bb &= pawns_bb - 1;
+    }
+}
+
+// En passant removes two pawns from the board at once, which the pin and check masks
+// cannot describe (e.g. both pawns shielding the king along the rank), so replay the
+// occupancy change and look for a slider on the king directly
+fn en_passant_is_legal(board: &Board, king_sq: u8, from: u8, ep: u8) -> bool {
+    let us = board.side_to_move;
+    let them = &board.bitboards[us.opposite() as usize];
+    let captured_sq = if us == Color::White { ep - 8 } else { ep + 8 };
+
+    // The only checker we can deal with this way is the pawn that just double-pushed
+    if board.checkers != 0 && board.checkers != 1u64 << captured_sq {
+        return false;
+    }
+
+    let occupied = (board.occupancies[2] ^ (1u64 << from) ^ (1u64 << captured_sq)) | (1u64 << ep);
+    let rook_like = them[Piece::Rook as usize] | them[Piece::Queen as usize];
+    let bishop_like = them[Piece::Bishop as usize] | them[Piece::Queen as usize];
+
+    ROOK_MAGICS_TABLE[king_sq as usize].attacks(occupied) & rook_like == 0
+        && BISHOP_MAGICS_TABLE[king_sq as usize].get_attacks(occupied) & bishop_like == 0
+}
+
+fn generate_castling_moves(board: &Board, king_sq: u8, list: &mut MoveList) {
+    let us = board.side_to_move;
+    let them = us.opposite();
+    let occupied = board.occupancies[2];
+
+    // (right, king start, king target, squares that must be empty, squares the king crosses)
+    let candidates = match us {
+        Color::White => [
+            (board.castling.white_kingside, 4, 6, 0x0000_0000_0000_0060u64, [4, 5, 6]),
+            (board.castling.white_queenside, 4, 2, 0x0000_0000_0000_000Eu64, [4, 3, 2]),
+        ],
+        Color::Black => [
+            (board.castling.black_kingside, 60, 62, 0x6000_0000_0000_0000u64, [60, 61, 62]),
+            (board.castling.black_queenside, 60, 58, 0x0E00_0000_0000_0000u64, [60, 59, 58]),
+        ],
+    };
+
+    for (allowed, from, to, empty, path) in candidates {
+        if !allowed || king_sq != from || occupied & empty != 0 {
+            continue;
+        }
+
+        // The king may not castle out of, through, or into check
+        if path.iter().any(|&sq| is_square_attacked(board, sq, them)) {
+            continue;
+        }
+
+        list.push(encode_move(from, to, FLAG_CASTLING, PROMO_NONE));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::r#move::{is_capture, is_promotion, move_flag, move_to_uci};
+
+    #[test]
+    fn promotions_that_capture_are_captures() {
+        let board = Board::from_fen("r3k3/1P6/8/8/8/8/8/4K3 w - - 0 1").expect("valid FEN");
+        let moves = generate_legal_moves(&board);
+        let flag = |uci: &str| moves.iter().copied().find(|&m| move_to_uci(m) == uci).map(move_flag);
+
+        // bxa8 under every promotion piece, and the push to b8
+        for uci in ["b7a8q", "b7a8r", "b7a8b", "b7a8n"] {
+            assert_eq!(flag(uci), Some(FLAG_PROMOTION_CAPTURE), "{}", uci);
+        }
+        for uci in ["b7b8q", "b7b8r", "b7b8b", "b7b8n"] {
+            assert_eq!(flag(uci), Some(FLAG_PROMOTION), "{}", uci);
+        }
+
+        let promotions: Vec<Move> = moves.iter().copied().filter(|&m| is_promotion(m)).collect();
+        assert_eq!(promotions.len(), 8);
+        assert_eq!(promotions.iter().filter(|&&m| is_capture(m)).count(), 4);
+    }
+}
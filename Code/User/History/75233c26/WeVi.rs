@@ -0,0 +1,70 @@
+use std::fmt;
+
+use crate::movegen::generate::generate_legal_moves;
+use crate::state::board::{Board, Color};
+
+/// Whether a position ends the game, and how
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GameResult {
+    Ongoing,
+    Checkmate(Color), // the side that delivered mate
+    Stalemate,
+    Repetition,
+    FiftyMoves,
+    InsufficientMaterial,
+}
+
+impl GameResult {
+    /// Result in PGN notation
+    pub fn score(self) -> &'static str {
+        match self {
+            GameResult::Ongoing => "*",
+            GameResult::Checkmate(Color::White) => "1-0",
+            GameResult::Checkmate(Color::Black) => "0-1",
+            _ => "1/2-1/2",
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self {
+            GameResult::Ongoing => "game in progress",
+            GameResult::Checkmate(Color::White) => "white mates",
+            GameResult::Checkmate(Color::Black) => "black mates",
+            GameResult::Stalemate => "stalemate",
+            GameResult::Repetition => "threefold repetition",
+            GameResult::FiftyMoves => "fifty-move rule",
+            GameResult::InsufficientMaterial => "insufficient material",
+        };
+        write!(f, "{} ({})", self.score(), reason)
+    }
+}
+
+/// Judge a position by the rules of the game. The board's key history supplies the
+/// earlier positions for threefold repetition.
+pub fn game_result(board: &Board) -> GameResult {
+    // Mate takes precedence, even when it lands on the hundredth half-move
+    if generate_legal_moves(board).is_empty() {
+        return if board.in_check() {
+            GameResult::Checkmate(board.side_to_move.opposite())
+        } else {
+            GameResult::Stalemate
+        };
+    }
+
+    if board.halfmove_clock >= 100 {
+        return GameResult::FiftyMoves;
+    }
+
+    if board.is_repetition(0) {
+        return GameResult::Repetition;
+    }
+
+    // Bare kings
+    if board.occupancies[2].count_ones() == 2 {
+        return GameResult::InsufficientMaterial;
+    }
+
+    GameResult::Ongoing
+}
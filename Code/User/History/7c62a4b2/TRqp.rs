@@ -0,0 +1,105 @@
+use crate::movegen::pawns::{Bitboard, A_FILE, H_FILE};
+use crate::state::board::{Board, Color, Piece};
+
+/// Squares of each colour (a1 is dark)
+pub const DARK_SQUARES: Bitboard = 0xAA55_AA55_AA55_AA55;
+pub const LIGHT_SQUARES: Bitboard = !DARK_SQUARES;
+
+/// Scale factors for the endgame half of the evaluation, out of SCALE_NORMAL
+pub const SCALE_NORMAL: i32 = 64;
+const SCALE_OPPOSITE_BISHOPS: i32 = 24;
+pub const SCALE_DRAW: i32 = 0;
+
+fn pieces(board: &Board, color: Color, piece: Piece) -> Bitboard {
+    board.bitboards[color as usize][piece as usize]
+}
+
+fn both(board: &Board, piece: Piece) -> Bitboard {
+    pieces(board, Color::White, piece) | pieces(board, Color::Black, piece)
+}
+
+/// Neither side can ever mate: bare kings, a single minor piece, or bishops only, all on
+/// squares of one colour
+pub fn is_insufficient_material(board: &Board) -> bool {
+    if both(board, Piece::Pawn) | both(board, Piece::Rook) | both(board, Piece::Queen) != 0 {
+        return false;
+    }
+
+    let knights = both(board, Piece::Knight);
+    let bishops = both(board, Piece::Bishop);
+
+    match (knights.count_ones(), bishops) {
+        (0 | 1, 0) => true,
+        (0, bishops) => bishops & DARK_SQUARES == 0 || bishops & LIGHT_SQUARES == 0,
+        _ => false,
+    }
+}
+
+/// How much of the endgame score `strong` (the side ahead) can expect to convert, out of
+/// SCALE_NORMAL
+pub fn scale_factor(board: &Board, strong: Color) -> i32 {
+    if is_insufficient_material(board) || is_wrong_rook_pawn(board, strong) {
+        return SCALE_DRAW;
+    }
+
+    if is_opposite_bishops(board) {
+        return SCALE_OPPOSITE_BISHOPS;
+    }
+
+    SCALE_NORMAL
+}
+
+// Bishop and rook pawns against a bare king that has reached the queening corner, when the
+// bishop doesn't cover the queening square
+fn is_wrong_rook_pawn(board: &Board, strong: Color) -> bool {
+    let weak = strong.opposite();
+    let pawns = pieces(board, strong, Piece::Pawn);
+    let bishops = pieces(board, strong, Piece::Bishop);
+
+    // Nothing else on the board but the two kings
+    let others = board.occupancies[2] & !(pawns | bishops | both(board, Piece::King));
+    if pawns == 0 || bishops.count_ones() != 1 || others != 0 {
+        return false;
+    }
+
+    let file = if pawns & !A_FILE == 0 {
+        0
+    } else if pawns & !H_FILE == 0 {
+        7
+    } else {
+        return false;
+    };
+
+    let queening_square = match strong {
+        Color::White => 56 + file,
+        Color::Black => file,
+    };
+    let queening_colour = if (1u64 << queening_square) & DARK_SQUARES != 0 {
+        DARK_SQUARES
+    } else {
+        LIGHT_SQUARES
+    };
+    if bishops & queening_colour != 0 {
+        return false;
+    }
+
+    // The defending king holds the draw from the corner or right next to it
+    let king = pieces(board, weak, Piece::King).trailing_zeros() as i32;
+    let rank_distance = (king / 8 - queening_square / 8).abs();
+    let file_distance = (king % 8 - queening_square % 8).abs();
+    rank_distance.max(file_distance) <= 1
+}
+
+// Each side has a single bishop, on opposite colours, and apart from pawns nothing else
+fn is_opposite_bishops(board: &Board) -> bool {
+    let white = pieces(board, Color::White, Piece::Bishop);
+    let black = pieces(board, Color::Black, Piece::Bishop);
+    if white.count_ones() != 1 || black.count_ones() != 1 {
+        return false;
+    }
+
+    let others = both(board, Piece::Knight) | both(board, Piece::Rook) | both(board, Piece::Queen);
+    let opposite = (white & DARK_SQUARES != 0) != (black & DARK_SQUARES != 0);
+
+    others == 0 && opposite
+}
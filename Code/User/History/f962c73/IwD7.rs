@@ -0,0 +1,10 @@
+mod movegen;
+mod bitboard;
+mod state;
+
+use crate::movegen::*;
+use bitboard::{Bitboards, print_bitboard};
+
+fn main() {
+
+}
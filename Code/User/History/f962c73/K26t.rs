@@ -0,0 +1,64 @@
+mod movegen;
+mod movepick;
+mod bitboard;
+mod eval;
+mod perft;
+mod search;
+mod see;
+mod state;
+mod tt;
+mod uci;
+
+use std::env;
+use std::process;
+
+use crate::state::board::{Board, START_FEN};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        // No arguments: speak UCI on stdin/stdout
+        None => uci::Uci::new().run(),
+
+        // chess-engine perft <depth> [fen]
+        // chess-engine divide <depth> [fen]
+        Some(mode @ ("perft" | "divide")) => {
+            let depth: u32 = match args.get(1).and_then(|d| d.parse().ok()) {
+                Some(depth) => depth,
+                None => {
+                    eprintln!("usage: chess-engine {} <depth> [fen]", mode);
+                    process::exit(2);
+                }
+            };
+
+            let fen = if args.len() > 2 { args[2..].join(" ") } else { START_FEN.to_string() };
+            let mut board = match Board::from_fen(&fen) {
+                Ok(board) => board,
+                Err(e) => {
+                    eprintln!("invalid fen: {}", e);
+                    process::exit(2);
+                }
+            };
+
+            if mode == "perft" {
+                perft::run(&mut board, depth);
+            } else {
+                perft::divide(&mut board, depth);
+            }
+        }
+        // chess-engine suite [max depth]
+        Some("suite") => {
+            let max_depth = args.get(1).and_then(|d| d.parse().ok()).unwrap_or(4);
+            if !perft::suite(max_depth) {
+                process::exit(1);
+            }
+        }
+        _ => {
+            eprintln!("usage: chess-engine");
+            eprintln!("       chess-engine <perft|divide> <depth> [fen]");
+            eprintln!("       chess-engine suite [max depth]");
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+mod movegen;
+mod movepick;
+mod bitboard;
+mod eval;
+mod perft;
+mod pgn;
+mod search;
+mod see;
+mod state;
+mod time;
+mod tt;
+mod uci;
+
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+use std::process;
+
+use crate::state::board::{Board, START_FEN};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        // No arguments: speak UCI on stdin/stdout
+        None => uci::Uci::new().run(),
+
+        // chess-engine perft <depth> [fen]
+        // chess-engine divide <depth> [fen]
+        Some(mode @ ("perft" | "divide")) => {
+            let depth: u32 = match args.get(1).and_then(|d| d.parse().ok()) {
+                Some(depth) => depth,
+                None => {
+                    eprintln!("usage: chess-engine {} <depth> [fen]", mode);
+                    process::exit(2);
+                }
+            };
+
+            let fen = if args.len() > 2 { args[2..].join(" ") } else { START_FEN.to_string() };
+            let mut board = match Board::from_fen(&fen) {
+                Ok(board) => board,
+                Err(e) => {
+                    eprintln!("invalid fen: {}", e);
+                    process::exit(2);
+                }
+            };
+
+            if mode == "perft" {
+                perft::run(&mut board, depth);
+            } else {
+                perft::divide(&mut board, depth);
+            }
+        }
+        // chess-engine suite [max depth]
+        Some("suite") => {
+            let max_depth = args.get(1).and_then(|d| d.parse().ok()).unwrap_or(4);
+            if !perft::suite(max_depth) {
+                process::exit(1);
+            }
+        }
+        // chess-engine pgn <file>: check that every game replays, and print it back out
+        Some("pgn") => {
+            let Some(path) = args.get(1) else {
+                eprintln!("usage: chess-engine pgn <file>");
+                process::exit(2);
+            };
+
+            let file = match File::open(path) {
+                Ok(file) => file,
+                Err(e) => {
+                    eprintln!("{}: {}", path, e);
+                    process::exit(2);
+                }
+            };
+
+            let mut failed = false;
+            for (i, game) in pgn::PgnReader::new(BufReader::new(file)).enumerate() {
+                match game.and_then(|game| pgn::write_game(&game)) {
+                    Ok(text) => print!("{}", text),
+                    Err(e) => {
+                        eprintln!("{}: game {}: {}", path, i + 1, e);
+                        failed = true;
+                    }
+                }
+            }
+
+            if failed {
+                process::exit(1);
+            }
+        }
+        _ => {
+            eprintln!("usage: chess-engine");
+            eprintln!("       chess-engine <perft|divide> <depth> [fen]");
+            eprintln!("       chess-engine suite [max depth]");
+            eprintln!("       chess-engine pgn <file>");
+            process::exit(2);
+        }
+    }
+}